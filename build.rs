@@ -1,18 +1,198 @@
 const COMMANDS: &[&str] = &[
     "check_accessibility_permission",
     "request_accessibility_permission",
+    "open_accessibility_settings",
     "check_full_disk_access_permission",
     "request_full_disk_access_permission",
+    "open_full_disk_access_settings",
     "check_screen_recording_permission",
     "request_screen_recording_permission",
+    "open_screen_recording_settings",
     "check_microphone_permission",
     "request_microphone_permission",
+    "open_microphone_settings",
     "check_camera_permission",
     "request_camera_permission",
+    "open_camera_settings",
     "check_input_monitoring_permission",
     "request_input_monitoring_permission",
+    "open_input_monitoring_settings",
+    "check_location_permission",
+    "request_location_permission",
+    "open_location_settings",
+    "get_current_position",
+    "check_notification_permission",
+    "request_notification_permission",
+    "open_notification_settings",
+    "list_capture_devices",
+    "verify_microphone_access",
+    "verify_screen_recording_access",
+    "verify_accessibility_access",
+    "check_event_tap_readiness",
+    "check_profile",
+    "has_been_requested",
+    "get_prompt_history",
+    "generate_pppc_profile",
+    "check_app_location",
+    "get_app_identity",
+    "find_app_copies",
+    "get_responsible_process",
+    "check_remote_session",
+    "is_virtual_machine",
+    "is_sip_enabled",
+    "get_plugin_info",
+    "get_permission_stats",
+    "request_path_access",
+    "check_global_shortcut_readiness",
+    "watch_global_shortcut_readiness",
+    "get_status_summary",
+    "get_os_upgrade",
+    "open_onboarding",
+    "dismiss_reminder",
+    "reset_reminders",
+    "check_tcc_service",
+    "open_settings_for_service",
+    "open_settings_and_wait",
+    "watch_permission",
+    "subscribe_permission_updates",
+    "unwatch",
+    "cancel",
+    "ensure_permission",
+    "check_permission",
 ];
 
+include!("src/privacy.rs");
+include!("src/mobileconfig.rs");
+
+/// Where to generate the app's privacy manifest, or the existing one to validate.
+const PRIVACY_MANIFEST_ENV: &str = "MACOS_PERMISSIONS_PRIVACY_MANIFEST";
+
+/// Where to generate a PPPC profile pre-approving the app's permissions.
+const PPPC_PROFILE_ENV: &str = "MACOS_PERMISSIONS_PPPC_PROFILE";
+
+/// The bundle identifier of the app the PPPC profile is for.
+const PPPC_IDENTIFIER_ENV: &str = "MACOS_PERMISSIONS_PPPC_IDENTIFIER";
+
+/// The designated requirement of the app, as printed by `codesign -dr - <app>`.
+const PPPC_REQUIREMENT_ENV: &str = "MACOS_PERMISSIONS_PPPC_REQUIREMENT";
+
+/// The permissions to pre-approve, e.g. `accessibility,fullDiskAccess`, every one a profile can grant when not set.
+const PPPC_PERMISSIONS_ENV: &str = "MACOS_PERMISSIONS_PPPC_PERMISSIONS";
+
 fn main() {
+    println!("cargo:rerun-if-env-changed={PRIVACY_MANIFEST_ENV}");
+
+    if let Some(path) = std::env::var_os(PRIVACY_MANIFEST_ENV) {
+        write_or_validate_privacy_manifest(std::path::Path::new(&path));
+    }
+
+    for env in [
+        PPPC_PROFILE_ENV,
+        PPPC_IDENTIFIER_ENV,
+        PPPC_REQUIREMENT_ENV,
+        PPPC_PERMISSIONS_ENV,
+    ] {
+        println!("cargo:rerun-if-env-changed={env}");
+    }
+
+    if let Some(path) = std::env::var_os(PPPC_PROFILE_ENV) {
+        write_pppc_profile(std::path::Path::new(&path));
+    }
+
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
+        export_sdk_info();
+    }
+
+    #[cfg(feature = "swift")]
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
+        swift_rs::SwiftLinker::new("10.15")
+            .with_package("MacosPermissions", "./swift/")
+            .link();
+    }
+
     tauri_plugin::Builder::new(COMMANDS).build();
 }
+
+/// Write the privacy manifest to `path`, or warn about the entries an existing one is missing.
+fn write_or_validate_privacy_manifest(path: &std::path::Path) {
+    // Privacy manifests are only reviewed for the App Store, where the feature is required anyway.
+    if std::env::var_os("CARGO_FEATURE_APP_STORE").is_none() {
+        println!("cargo:warning=Privacy manifests are for App Store builds, enable the `app-store` feature");
+    }
+
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    match std::fs::read_to_string(path) {
+        Ok(manifest) => {
+            for entry in missing_privacy_entries(&manifest) {
+                println!("cargo:warning={} does not declare {entry}", path.display());
+            }
+        }
+        Err(_) => {
+            if let Err(error) = std::fs::write(path, privacy_manifest()) {
+                println!("cargo:warning=Failed to write {}: {error}", path.display());
+            }
+        }
+    }
+}
+
+/// Write a PPPC profile for the app to `path`, warning about the permissions a profile cannot grant.
+fn write_pppc_profile(path: &std::path::Path) {
+    let (Ok(identifier), Ok(code_requirement)) = (
+        std::env::var(PPPC_IDENTIFIER_ENV),
+        std::env::var(PPPC_REQUIREMENT_ENV),
+    ) else {
+        println!("cargo:warning={PPPC_PROFILE_ENV} needs {PPPC_IDENTIFIER_ENV} and {PPPC_REQUIREMENT_ENV}");
+
+        return;
+    };
+
+    let permissions = std::env::var(PPPC_PERMISSIONS_ENV)
+        .unwrap_or_else(|_| PPPC_SERVICES.map(|(permission, _, _)| permission).join(","));
+
+    let services: Vec<_> = permissions
+        .split(',')
+        .map(str::trim)
+        .filter(|permission| !permission.is_empty())
+        .filter_map(|permission| {
+            let service = pppc_service(permission);
+
+            if service.is_none() {
+                println!("cargo:warning={permission} cannot be granted by a PPPC profile");
+            }
+
+            service
+        })
+        .collect();
+
+    let profile = render_mobileconfig(&identifier, &code_requirement, None, &services);
+
+    if let Err(error) = std::fs::write(path, profile) {
+        println!("cargo:warning=Failed to write {}: {error}", path.display());
+    }
+}
+
+/// Remember the macOS SDK and deployment target of the build, reported by `get_plugin_info`.
+fn export_sdk_info() {
+    println!("cargo:rerun-if-env-changed=MACOSX_DEPLOYMENT_TARGET");
+    println!("cargo:rerun-if-env-changed=SDKROOT");
+
+    if let Ok(target) = std::env::var("MACOSX_DEPLOYMENT_TARGET") {
+        println!("cargo:rustc-env=MACOS_PERMISSIONS_DEPLOYMENT_TARGET={target}");
+    }
+
+    // Missing when cross-compiling without Xcode.
+    let sdk_version = std::process::Command::new("xcrun")
+        .args(["--sdk", "macosx", "--show-sdk-version"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+
+    if let Some(version) = sdk_version {
+        println!(
+            "cargo:rustc-env=MACOS_PERMISSIONS_SDK_VERSION={}",
+            version.trim()
+        );
+    }
+}
@@ -2,6 +2,7 @@
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_macos_permissions::init())
+        .on_permission_request(tauri_plugin_macos_permissions::webview_permission_handler)
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -1,29 +1,609 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+export type PermissionKind =
+  | "accessibility"
+  | "fullDiskAccess"
+  | "screenRecording"
+  | "microphone"
+  | "camera"
+  | "inputMonitoring"
+  | "location"
+  | "notifications";
+
+export type PermissionStatus =
+  | "granted"
+  | "denied"
+  | "notDetermined"
+  | "restricted"
+  | "unsupported";
+
+export interface PermissionDetails {
+  trusted?: boolean;
+  probePath?: string;
+  preflight?: boolean;
+  authorizationStatus?: number;
+  hidAccess?: number;
+}
+
+export interface PermissionState {
+  kind: PermissionKind;
+  status: PermissionStatus;
+  /**
+   * Whether a matching capture device is connected, only set for microphone and camera.
+   */
+  deviceConnected?: boolean;
+  /**
+   * Whether a configuration profile controls the permission, so the user cannot change it.
+   * Not set if this cannot be determined.
+   */
+  managed?: boolean;
+  details?: PermissionDetails;
+}
+
+export interface Position {
+  latitude: number;
+  longitude: number;
+  accuracy: number;
+  altitude: number | null;
+  altitudeAccuracy: number | null;
+  timestamp: number;
+}
+
+/**
+ * What happened when a permission was requested.
+ */
+export type RequestOutcome =
+  | "granted"
+  | "denied"
+  | "dismissed"
+  | "openedSettings"
+  | "promptUnavailable"
+  | "remoteSession"
+  | "unsupported";
+
+export interface AccessibilityRequestOptions {
+  /**
+   * Show the system prompt, it only appears once per install. Defaults to `true`.
+   */
+  prompt?: boolean;
+  /**
+   * Open the Accessibility pane of System Settings, ignored with the `headlessRequests` config or the `prompt` request strategy. Defaults to `false`.
+   */
+  openSettings?: boolean;
+}
+
+export interface EnsurePermissionOptions {
+  /**
+   * Request the permission if it is missing, following its request strategy. Defaults to `true`.
+   */
+  request?: boolean;
+  /**
+   * Wait until the permission is granted after System Settings was opened, bounded by the `requestTimeout` config. Defaults to `false`.
+   */
+  wait?: boolean;
+  /**
+   * Stops early when aborted, rejecting with a `cancelled` {@link PermissionError}.
+   */
+  signal?: AbortSignal;
+}
+
+export interface EnsuredPermission {
+  kind: PermissionKind;
+  /**
+   * Whether the permission is granted in the end.
+   */
+  granted: boolean;
+  /**
+   * The status of the permission in the end.
+   */
+  status: PermissionStatus;
+  /**
+   * What the request did, missing if the permission was already granted or not requested.
+   */
+  outcome?: RequestOutcome;
+}
+
+export type MediaType = "audio" | "video";
+
+export interface CaptureDevice {
+  id: string;
+  name: string;
+  modelId: string;
+}
+
+export type VerificationStatus = "granted" | "ineffective" | "denied";
+
+export interface Verification {
+  status: VerificationStatus;
+  /**
+   * Why the permission is not effective.
+   */
+  reason?: string;
+}
+
+export interface EventTapReadiness {
+  /**
+   * Whether a keyboard event tap can be installed.
+   */
+  ready: boolean;
+  accessibility: boolean;
+  inputMonitoring: boolean;
+  /**
+   * Whether a test event tap could be created.
+   */
+  eventTap: boolean;
+  /**
+   * The permissions that still have to be granted.
+   */
+  missing: PermissionKind[];
+}
+
+export type Profile =
+  | "screen-recorder"
+  | "clipboard-manager"
+  | "automation-tool"
+  | "meeting-app";
+
+export interface ProfileReadiness {
+  profile: Profile;
+  /**
+   * Whether every permission of the profile is granted.
+   */
+  ready: boolean;
+  granted: PermissionKind[];
+  missing: PermissionKind[];
+}
+
+/**
+ * How the user is connected to the Mac remotely.
+ */
+export type RemoteSessionKind = "ssh" | "screenSharing" | "remoteManagement";
+
+export interface RemoteSession {
+  /**
+   * How the user is connected.
+   */
+  kind: RemoteSessionKind;
+  /**
+   * What the user should do to grant permissions, ready to be shown.
+   */
+  guidance: string;
+}
+
+export interface PromptHistory {
+  kind: PermissionKind;
+  /**
+   * Milliseconds since the Unix epoch the user answered the prompt, unknown once the decision was changed in System Settings since.
+   */
+  lastPrompted?: number;
+  /**
+   * Milliseconds since the Unix epoch the decision was last changed, by answering the prompt or in System Settings.
+   */
+  lastModified: number;
+  /**
+   * Milliseconds since the Unix epoch macOS last reminded the user of the grant, as it does monthly for screen recording.
+   */
+  lastReminded?: number;
+}
+
+export interface AppIdentity {
+  /**
+   * The path of the running executable.
+   */
+  executablePath: string;
+  /**
+   * The bundle identifier, missing for a bare binary, e.g. from `cargo run`.
+   */
+  bundleId?: string;
+  /**
+   * The team identifier of the signing certificate, missing for ad-hoc and unsigned builds.
+   */
+  teamId?: string;
+  /**
+   * The signing certificate, `adhoc` for ad-hoc signed builds, missing for unsigned ones.
+   */
+  signingIdentity?: string;
+  /**
+   * Whether the app runs from a `.app` bundle, grants to a bare binary are given to the terminal running it instead.
+   */
+  bundled: boolean;
+}
+
+export interface AppCopy {
+  /**
+   * The path of the app bundle.
+   */
+  path: string;
+  /**
+   * The `CFBundleShortVersionString` of the copy.
+   */
+  version?: string;
+  /**
+   * Whether the running process is this copy.
+   */
+  current: boolean;
+  /**
+   * Whether the copy is signed like the running one, so TCC applies the same grants to both.
+   * Missing when the signatures cannot be read, e.g. with the `app-store` feature.
+   */
+  sharesGrants?: boolean;
+}
+
+export interface AppCopies {
+  /**
+   * Every copy with the bundle identifier of the running app, the running one included.
+   */
+  copies: AppCopy[];
+  /**
+   * The path of the copy the user most likely granted the permissions to, when it is not the running one.
+   */
+  likelyGranted?: string;
+}
+
+export interface ResponsibleProcess {
+  pid: number;
+  /**
+   * The executable path of the process.
+   */
+  path: string;
+  /**
+   * The outermost app bundle containing it, e.g. `/System/Applications/Utilities/Terminal.app`.
+   */
+  bundlePath?: string;
+  /**
+   * The bundle identifier of that bundle, e.g. `com.apple.Terminal`.
+   */
+  bundleId?: string;
+  /**
+   * Whether the app is responsible for itself, the grants listed for it in System Settings are then its own.
+   */
+  isSelf: boolean;
+  /**
+   * What to tell the developer when another process is responsible, missing otherwise.
+   */
+  guidance?: string;
+}
+
+export interface PluginInfo {
+  /**
+   * The version of the plugin, e.g. `2.3.0`.
+   */
+  version: string;
+  /**
+   * The macOS SDK the plugin was built against, missing when it was built without Xcode.
+   */
+  sdkVersion?: string;
+  /**
+   * The `MACOSX_DEPLOYMENT_TARGET` the plugin was built with, missing when it was not set.
+   */
+  deploymentTarget?: string;
+  /**
+   * The enabled features of the plugin, e.g. `["app-store", "specta"]`.
+   */
+  features: string[];
+  /**
+   * The running macOS version, e.g. `15.1`, missing on other platforms.
+   */
+  osVersion?: string;
+  /**
+   * Whether System Integrity Protection is enabled, missing when it cannot be checked, see `isSipEnabled`.
+   */
+  sipEnabled?: boolean;
+}
+
+export interface PermissionStats {
+  /**
+   * Status queries to the system, by checks, watchers and polling.
+   */
+  checks: number;
+  /**
+   * System prompts shown by requests.
+   */
+  promptsShown: number;
+  /**
+   * Times System Settings was opened by requests, onboarding, the tray menu or `openSettingsAndWait`.
+   */
+  settingsOpened: number;
+  /**
+   * Permissions seen changing to granted.
+   */
+  grantsObserved: number;
+  /**
+   * Milliseconds since the plugin was set up.
+   */
+  elapsed: number;
+}
+
+export interface AppLocation {
+  /**
+   * The path of the app bundle.
+   */
+  path: string;
+  /**
+   * Whether Gatekeeper runs the app from a randomized read-only copy, grants to it do not persist.
+   */
+  translocated: boolean;
+  /**
+   * Whether the app still carries the quarantine attribute of a download.
+   */
+  quarantined: boolean;
+}
+
+export type PermissionErrorCode =
+  | "permissionDenied"
+  | "requestFailed"
+  | "timedOut"
+  | "cancelled"
+  | "promptUnavailable"
+  | "noGuiSession"
+  | "invalidService"
+  | "cancelIdInUse"
+  | "unsupported";
+
+const PERMISSION_ERROR_CODES: PermissionErrorCode[] = [
+  "permissionDenied",
+  "requestFailed",
+  "timedOut",
+  "cancelled",
+  "promptUnavailable",
+  "noGuiSession",
+  "invalidService",
+  "cancelIdInUse",
+  "unsupported",
+];
+
+/**
+ * The rejection of every command of the plugin, and of commands guarded by `requires_permission` or `PermissionGate`.
+ *
+ * Switch on `code` instead of parsing `message`, which is only meant for logs.
+ */
+export interface PermissionError {
+  code: PermissionErrorCode;
+  message: string;
+  /**
+   * The missing, timed out or cancelled permission.
+   */
+  permission?: PermissionKind;
+}
+
+export type ShortcutBlocker =
+  | "noGuiSession"
+  | "accessibilityMissing"
+  | "inputMonitoringMissing"
+  | "eventTapUnavailable";
+
+export interface ShortcutReadiness {
+  ready: boolean;
+  blocker?: ShortcutBlocker;
+}
+
+export interface PermissionStatusEntry {
+  kind: PermissionKind;
+  status: PermissionStatus;
+}
+
+export interface StatusChange {
+  kind: PermissionKind;
+  status: PermissionStatus;
+  previous: PermissionStatus;
+}
+
+export type PermissionUpdate =
+  | { type: "snapshot"; statuses: PermissionStatusEntry[] }
+  | ({ type: "changed" } & StatusChange)
+  | { type: "batch"; changes: StatusChange[] };
+
+export interface PermissionChanged {
+  id: number;
+  kind: PermissionKind;
+  authorized: boolean;
+}
+
+export interface PermissionsChanged {
+  changes: PermissionChanged[];
+}
+
+export interface StatusSummary {
+  ok: boolean;
+  permissions: PermissionStatusEntry[];
+  missing: PermissionKind[];
+}
+
+export interface OnboardingFinished {
+  completed: boolean;
+  missing: PermissionKind[];
+}
+
+export interface Reminder {
+  kind: PermissionKind;
+  count: number;
+}
+
+export interface OsUpgraded {
+  /**
+   * The version of the last launch, e.g. `14.6`.
+   */
+  previousVersion: string;
+  /**
+   * The running version, e.g. `15.1`.
+   */
+  version: string;
+  /**
+   * The re-verified status of the permissions of the `permissions` config.
+   */
+  summary: StatusSummary;
+}
+
+export interface GrantCompleted {
+  kind: PermissionKind;
+  refocused: boolean;
+}
+
+export type PathAccess =
+  | "readable"
+  | "filesAndFolders"
+  | "fullDiskAccess"
+  | "sandbox"
+  | "denied"
+  | "missing";
+
+export interface PathAccessEntry {
+  path: string;
+  access: PathAccess;
+}
+
+export interface FileDropResolved {
+  /**
+   * The label of the window or webview the files were dropped on.
+   */
+  label: string;
+  /**
+   * The access of each dropped path.
+   */
+  paths: PathAccessEntry[];
+  /**
+   * Whether all dropped paths can be read.
+   */
+  ready: boolean;
+  /**
+   * What the request of the missing access did, missing if nothing was requested.
+   */
+  outcome?: RequestOutcome;
+}
 
 export const COMMAND = {
   CHECK_ACCESSIBILITY_PERMISSION:
     "plugin:macos-permissions|check_accessibility_permission",
   REQUEST_ACCESSIBILITY_PERMISSION:
     "plugin:macos-permissions|request_accessibility_permission",
+  OPEN_ACCESSIBILITY_SETTINGS:
+    "plugin:macos-permissions|open_accessibility_settings",
   CHECK_FULL_DISK_ACCESS_PERMISSION:
     "plugin:macos-permissions|check_full_disk_access_permission",
   REQUEST_FULL_DISK_ACCESS_PERMISSION:
     "plugin:macos-permissions|request_full_disk_access_permission",
+  OPEN_FULL_DISK_ACCESS_SETTINGS:
+    "plugin:macos-permissions|open_full_disk_access_settings",
   CHECK_SCREEN_RECORDING_PERMISSION:
     "plugin:macos-permissions|check_screen_recording_permission",
   REQUEST_SCREEN_RECORDING_PERMISSION:
     "plugin:macos-permissions|request_screen_recording_permission",
+  OPEN_SCREEN_RECORDING_SETTINGS:
+    "plugin:macos-permissions|open_screen_recording_settings",
   CHECK_MICROPHONE_PERMISSION:
     "plugin:macos-permissions|check_microphone_permission",
   REQUEST_MICROPHONE_PERMISSION:
     "plugin:macos-permissions|request_microphone_permission",
+  OPEN_MICROPHONE_SETTINGS: "plugin:macos-permissions|open_microphone_settings",
   CHECK_CAMERA_PERMISSION: "plugin:macos-permissions|check_camera_permission",
   REQUEST_CAMERA_PERMISSION:
     "plugin:macos-permissions|request_camera_permission",
+  OPEN_CAMERA_SETTINGS: "plugin:macos-permissions|open_camera_settings",
   CHECK_INPUT_MONITORING_PERMISSION:
     "plugin:macos-permissions|check_input_monitoring_permission",
   REQUEST_INPUT_MONITORING_PERMISSION:
     "plugin:macos-permissions|request_input_monitoring_permission",
+  OPEN_INPUT_MONITORING_SETTINGS:
+    "plugin:macos-permissions|open_input_monitoring_settings",
+  CHECK_LOCATION_PERMISSION:
+    "plugin:macos-permissions|check_location_permission",
+  REQUEST_LOCATION_PERMISSION:
+    "plugin:macos-permissions|request_location_permission",
+  OPEN_LOCATION_SETTINGS: "plugin:macos-permissions|open_location_settings",
+  GET_CURRENT_POSITION: "plugin:macos-permissions|get_current_position",
+  CHECK_NOTIFICATION_PERMISSION:
+    "plugin:macos-permissions|check_notification_permission",
+  REQUEST_NOTIFICATION_PERMISSION:
+    "plugin:macos-permissions|request_notification_permission",
+  OPEN_NOTIFICATION_SETTINGS:
+    "plugin:macos-permissions|open_notification_settings",
+  LIST_CAPTURE_DEVICES: "plugin:macos-permissions|list_capture_devices",
+  VERIFY_MICROPHONE_ACCESS:
+    "plugin:macos-permissions|verify_microphone_access",
+  VERIFY_SCREEN_RECORDING_ACCESS:
+    "plugin:macos-permissions|verify_screen_recording_access",
+  VERIFY_ACCESSIBILITY_ACCESS:
+    "plugin:macos-permissions|verify_accessibility_access",
+  CHECK_EVENT_TAP_READINESS:
+    "plugin:macos-permissions|check_event_tap_readiness",
+  CHECK_PROFILE: "plugin:macos-permissions|check_profile",
+  HAS_BEEN_REQUESTED: "plugin:macos-permissions|has_been_requested",
+  GET_PROMPT_HISTORY: "plugin:macos-permissions|get_prompt_history",
+  GENERATE_PPPC_PROFILE: "plugin:macos-permissions|generate_pppc_profile",
+  CHECK_APP_LOCATION: "plugin:macos-permissions|check_app_location",
+  GET_APP_IDENTITY: "plugin:macos-permissions|get_app_identity",
+  FIND_APP_COPIES: "plugin:macos-permissions|find_app_copies",
+  GET_RESPONSIBLE_PROCESS: "plugin:macos-permissions|get_responsible_process",
+  CHECK_REMOTE_SESSION: "plugin:macos-permissions|check_remote_session",
+  IS_VIRTUAL_MACHINE: "plugin:macos-permissions|is_virtual_machine",
+  IS_SIP_ENABLED: "plugin:macos-permissions|is_sip_enabled",
+  GET_PLUGIN_INFO: "plugin:macos-permissions|get_plugin_info",
+  GET_PERMISSION_STATS: "plugin:macos-permissions|get_permission_stats",
+  REQUEST_PATH_ACCESS: "plugin:macos-permissions|request_path_access",
+  CHECK_GLOBAL_SHORTCUT_READINESS:
+    "plugin:macos-permissions|check_global_shortcut_readiness",
+  WATCH_GLOBAL_SHORTCUT_READINESS:
+    "plugin:macos-permissions|watch_global_shortcut_readiness",
+  GET_STATUS_SUMMARY: "plugin:macos-permissions|get_status_summary",
+  GET_OS_UPGRADE: "plugin:macos-permissions|get_os_upgrade",
+  OPEN_ONBOARDING: "plugin:macos-permissions|open_onboarding",
+  DISMISS_REMINDER: "plugin:macos-permissions|dismiss_reminder",
+  RESET_REMINDERS: "plugin:macos-permissions|reset_reminders",
+  CHECK_TCC_SERVICE: "plugin:macos-permissions|check_tcc_service",
+  OPEN_SETTINGS_FOR_SERVICE:
+    "plugin:macos-permissions|open_settings_for_service",
+  OPEN_SETTINGS_AND_WAIT: "plugin:macos-permissions|open_settings_and_wait",
+  WATCH_PERMISSION: "plugin:macos-permissions|watch_permission",
+  SUBSCRIBE_PERMISSION_UPDATES:
+    "plugin:macos-permissions|subscribe_permission_updates",
+  UNWATCH: "plugin:macos-permissions|unwatch",
+  CANCEL: "plugin:macos-permissions|cancel",
+  ENSURE_PERMISSION: "plugin:macos-permissions|ensure_permission",
+  CHECK_PERMISSION: "plugin:macos-permissions|check_permission",
+};
+
+export const EVENT = {
+  PERMISSION_CHANGED: "macos-permissions://permission-changed",
+  PERMISSIONS_CHANGED: "macos-permissions://permissions-changed",
+  GLOBAL_SHORTCUT_READY: "macos-permissions://global-shortcut-ready",
+  ONBOARDING_FINISHED: "macos-permissions://onboarding-finished",
+  REMINDER: "macos-permissions://reminder",
+  GRANT_COMPLETED: "macos-permissions://grant-completed",
+  OS_UPGRADED: "macos-permissions://os-upgraded",
+  FILE_DROP_RESOLVED: "macos-permissions://file-drop-resolved",
+};
+
+/**
+ * Listen to the events under the `eventPrefix` config, must match it.
+ *
+ * @param prefix The `eventPrefix` of the plugin config.
+ *
+ * @example
+ * import { EVENT, setEventPrefix } from "tauri-plugin-macos-permissions-api";
+ *
+ * setEventPrefix("acme-permissions");
+ * console.log(EVENT.REMINDER); // "acme-permissions://reminder"
+ */
+export const setEventPrefix = (prefix: string) => {
+  for (const key of Object.keys(EVENT) as Array<keyof typeof EVENT>) {
+    EVENT[key] = EVENT[key].replace(/^[^:]*:\/\//, `${prefix}://`);
+  }
+};
+
+const deprecationsWarned = new Set<string>();
+
+/** Run the boolean `command` of earlier versions, warning once that `name` is deprecated in favor of checking `kind`. */
+const checkDeprecated = (name: string, kind: PermissionKind, command: string) => {
+  if (!deprecationsWarned.has(name)) {
+    deprecationsWarned.add(name);
+
+    console.warn(
+      `${name} is deprecated, use checkPermission("${kind}") instead.`,
+    );
+  }
+
+  return invoke<boolean>(command);
 };
 
 /**
@@ -31,6 +611,8 @@ export const COMMAND = {
  *
  * @returns `true` if accessibility permission are granted, `false` otherwise.
  *
+ * @deprecated Use `checkPermission("accessibility")` instead, this still resolves as in earlier versions.
+ *
  * @example
  * import { checkAccessibilityPermission } from "tauri-plugin-macos-permissions-api";
  *
@@ -38,19 +620,49 @@ export const COMMAND = {
  * console.log(authorized); // false
  */
 export const checkAccessibilityPermission = () => {
-  return invoke<boolean>(COMMAND.CHECK_ACCESSIBILITY_PERMISSION);
+  return checkDeprecated(
+    "checkAccessibilityPermission",
+    "accessibility",
+    COMMAND.CHECK_ACCESSIBILITY_PERMISSION
+  );
 };
 
 /**
  * Request accessibility permission.
  *
+ * Rejects with a `timedOut` {@link PermissionError} once the `requestTimeout` config elapses.
+ *
+ * @param options Whether to show the system prompt, open System Settings, or both. Defaults to only showing the prompt.
+ * @returns `granted`, `dismissed` once the prompt is closed, `openedSettings`, or `promptUnavailable` if the user
+ * was already asked.
+ *
  * @example
  * import { requestAccessibilityPermission } from "tauri-plugin-macos-permissions-api";
  *
- * await requestAccessibilityPermission();
+ * const outcome = await requestAccessibilityPermission();
+ *
+ * if (outcome === "promptUnavailable") {
+ *   await requestAccessibilityPermission({ prompt: false, openSettings: true });
+ * }
+ */
+export const requestAccessibilityPermission = (
+  options?: AccessibilityRequestOptions
+) => {
+  return invoke<RequestOutcome>(COMMAND.REQUEST_ACCESSIBILITY_PERMISSION, {
+    options,
+  });
+};
+
+/**
+ * Open the Accessibility pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openAccessibilitySettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openAccessibilitySettings();
  */
-export const requestAccessibilityPermission = () => {
-  return invoke(COMMAND.REQUEST_ACCESSIBILITY_PERMISSION);
+export const openAccessibilitySettings = () => {
+  return invoke(COMMAND.OPEN_ACCESSIBILITY_SETTINGS);
 };
 
 /**
@@ -58,6 +670,8 @@ export const requestAccessibilityPermission = () => {
  *
  * @returns `true` if full disk access permission are granted, `false` otherwise.
  *
+ * @deprecated Use `checkPermission("fullDiskAccess")` instead, this still resolves as in earlier versions.
+ *
  * @example
  * import { checkFullDiskAccessPermission } from "tauri-plugin-macos-permissions-api";
  *
@@ -65,19 +679,40 @@ export const requestAccessibilityPermission = () => {
  * console.log(authorized); // false
  */
 export const checkFullDiskAccessPermission = () => {
-  return invoke<boolean>(COMMAND.CHECK_FULL_DISK_ACCESS_PERMISSION);
+  return checkDeprecated(
+    "checkFullDiskAccessPermission",
+    "fullDiskAccess",
+    COMMAND.CHECK_FULL_DISK_ACCESS_PERMISSION
+  );
 };
 
 /**
  * Request full disk access permission.
  *
+ * There is no system prompt for full disk access, this opens System Settings like `openFullDiskAccessSettings`.
+ *
+ * @returns `granted`, or `openedSettings` if the permission is missing.
+ *
  * @example
  * import { requestFullDiskAccessPermission } from "tauri-plugin-macos-permission-api";
  *
- * await requestFullDiskAccessPermission();
+ * const outcome = await requestFullDiskAccessPermission();
+ * console.log(outcome); // "openedSettings"
  */
 export const requestFullDiskAccessPermission = () => {
-  return invoke(COMMAND.REQUEST_FULL_DISK_ACCESS_PERMISSION);
+  return invoke<RequestOutcome>(COMMAND.REQUEST_FULL_DISK_ACCESS_PERMISSION);
+};
+
+/**
+ * Open the Full Disk Access pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openFullDiskAccessSettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openFullDiskAccessSettings();
+ */
+export const openFullDiskAccessSettings = () => {
+  return invoke(COMMAND.OPEN_FULL_DISK_ACCESS_SETTINGS);
 };
 
 /**
@@ -85,6 +720,8 @@ export const requestFullDiskAccessPermission = () => {
  *
  * @returns `true` if screen recording permission are granted, `false` otherwise.
  *
+ * @deprecated Use `checkPermission("screenRecording")` instead, this still resolves as in earlier versions.
+ *
  * @example
  * import { checkScreenRecordingPermission } from "tauri-plugin-macos-permissions-api";
  *
@@ -92,19 +729,42 @@ export const requestFullDiskAccessPermission = () => {
  * console.log(authorized); // false
  */
 export const checkScreenRecordingPermission = () => {
-  return invoke<boolean>(COMMAND.CHECK_SCREEN_RECORDING_PERMISSION);
+  return checkDeprecated(
+    "checkScreenRecordingPermission",
+    "screenRecording",
+    COMMAND.CHECK_SCREEN_RECORDING_PERMISSION
+  );
 };
 
 /**
  * Request screen recording permission.
  *
+ * Resolves once the user responded to the system prompt. The prompt only appears the first time,
+ * afterwards the permission can only be granted in System Settings. Rejects with a `timedOut`
+ * {@link PermissionError} once the `requestTimeout` config elapses.
+ *
+ * @returns `granted`, `dismissed` once the prompt is closed, or `promptUnavailable` if the user was already asked.
+ *
  * @example
  * import { requestScreenRecordingPermission } from "tauri-plugin-macos-permissions-api";
  *
- * await requestScreenRecordingPermission();
+ * const outcome = await requestScreenRecordingPermission();
+ * console.log(outcome); // "dismissed"
  */
 export const requestScreenRecordingPermission = () => {
-  return invoke(COMMAND.REQUEST_SCREEN_RECORDING_PERMISSION);
+  return invoke<RequestOutcome>(COMMAND.REQUEST_SCREEN_RECORDING_PERMISSION);
+};
+
+/**
+ * Open the Screen Recording pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openScreenRecordingSettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openScreenRecordingSettings();
+ */
+export const openScreenRecordingSettings = () => {
+  return invoke(COMMAND.OPEN_SCREEN_RECORDING_SETTINGS);
 };
 
 /**
@@ -112,6 +772,8 @@ export const requestScreenRecordingPermission = () => {
  *
  * @returns `true` if microphone permission are granted, `false` otherwise.
  *
+ * @deprecated Use `checkPermission("microphone")` instead, this still resolves as in earlier versions.
+ *
  * @example
  * import { checkMicrophonePermission } from "tauri-plugin-macos-permissions-api";
  *
@@ -119,19 +781,41 @@ export const requestScreenRecordingPermission = () => {
  * console.log(authorized); // false
  */
 export const checkMicrophonePermission = () => {
-  return invoke<boolean>(COMMAND.CHECK_MICROPHONE_PERMISSION);
+  return checkDeprecated(
+    "checkMicrophonePermission",
+    "microphone",
+    COMMAND.CHECK_MICROPHONE_PERMISSION
+  );
 };
 
 /**
  * Request microphone permission.
  *
+ * Resolves once the user responded to the system prompt. Rejects with a `timedOut` {@link PermissionError} once
+ * the `requestTimeout` config elapses.
+ *
+ * @returns `granted`, `denied`, or `promptUnavailable` if the permission is restricted.
+ *
  * @example
  * import { requestMicrophonePermission } from "tauri-plugin-macos-permissions-api";
  *
- * await requestMicrophonePermission();
+ * const outcome = await requestMicrophonePermission();
+ * console.log(outcome); // "granted"
  */
 export const requestMicrophonePermission = () => {
-  return invoke(COMMAND.REQUEST_MICROPHONE_PERMISSION);
+  return invoke<RequestOutcome>(COMMAND.REQUEST_MICROPHONE_PERMISSION);
+};
+
+/**
+ * Open the Microphone pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openMicrophoneSettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openMicrophoneSettings();
+ */
+export const openMicrophoneSettings = () => {
+  return invoke(COMMAND.OPEN_MICROPHONE_SETTINGS);
 };
 
 /**
@@ -139,6 +823,8 @@ export const requestMicrophonePermission = () => {
  *
  * @returns `true` if camera permission are granted, `false` otherwise.
  *
+ * @deprecated Use `checkPermission("camera")` instead, this still resolves as in earlier versions.
+ *
  * @example
  * import { checkCameraPermission } from "tauri-plugin-macos-permissions-api";
  *
@@ -146,19 +832,41 @@ export const requestMicrophonePermission = () => {
  * console.log(authorized); // false
  */
 export const checkCameraPermission = () => {
-  return invoke<boolean>(COMMAND.CHECK_CAMERA_PERMISSION);
+  return checkDeprecated(
+    "checkCameraPermission",
+    "camera",
+    COMMAND.CHECK_CAMERA_PERMISSION
+  );
 };
 
 /**
  * Request camera permission.
  *
+ * Resolves once the user responded to the system prompt. Rejects with a `timedOut` {@link PermissionError} once
+ * the `requestTimeout` config elapses.
+ *
+ * @returns `granted`, `denied`, or `promptUnavailable` if the permission is restricted.
+ *
  * @example
  * import { requestCameraPermission } from "tauri-plugin-macos-permissions-api";
  *
- * await requestCameraPermission();
+ * const outcome = await requestCameraPermission();
+ * console.log(outcome); // "granted"
  */
 export const requestCameraPermission = () => {
-  return invoke(COMMAND.REQUEST_CAMERA_PERMISSION);
+  return invoke<RequestOutcome>(COMMAND.REQUEST_CAMERA_PERMISSION);
+};
+
+/**
+ * Open the Camera pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openCameraSettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openCameraSettings();
+ */
+export const openCameraSettings = () => {
+  return invoke(COMMAND.OPEN_CAMERA_SETTINGS);
 };
 
 /**
@@ -166,6 +874,8 @@ export const requestCameraPermission = () => {
  *
  * @returns `true` if input monitoring permission are granted, `false` otherwise.
  *
+ * @deprecated Use `checkPermission("inputMonitoring")` instead, this still resolves as in earlier versions.
+ *
  * @example
  * import { checkInputMonitoringPermission } from "tauri-plugin-macos-permissions-api";
  *
@@ -173,17 +883,1287 @@ export const requestCameraPermission = () => {
  * console.log(authorized); // false
  */
 export const checkInputMonitoringPermission = () => {
-  return invoke<boolean>(COMMAND.CHECK_INPUT_MONITORING_PERMISSION);
+  return checkDeprecated(
+    "checkInputMonitoringPermission",
+    "inputMonitoring",
+    COMMAND.CHECK_INPUT_MONITORING_PERMISSION
+  );
 };
 
 /**
  * Request input monitoring permission.
  *
+ * Shows the system prompt, it only appears once per app. Rejects with a `timedOut` {@link PermissionError} once
+ * the `requestTimeout` config elapses.
+ *
+ * @returns `granted`, `denied`, `dismissed` once the prompt is closed, or `promptUnavailable` if the user was
+ * already asked.
+ *
  * @example
  * import { requestInputMonitoringPermission } from "tauri-plugin-macos-permissions-api";
  *
- * await requestInputMonitoringPermission();
+ * const outcome = await requestInputMonitoringPermission();
+ * console.log(outcome); // "denied"
  */
 export const requestInputMonitoringPermission = () => {
-  return invoke(COMMAND.REQUEST_INPUT_MONITORING_PERMISSION);
+  return invoke<RequestOutcome>(COMMAND.REQUEST_INPUT_MONITORING_PERMISSION);
+};
+
+/**
+ * Open the Input Monitoring pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openInputMonitoringSettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openInputMonitoringSettings();
+ */
+export const openInputMonitoringSettings = () => {
+  return invoke(COMMAND.OPEN_INPUT_MONITORING_SETTINGS);
+};
+
+/**
+ * Check location permission.
+ *
+ * @returns `true` if location permission are granted, `false` otherwise.
+ *
+ * @deprecated Use `checkPermission("location")` instead, this still resolves as in earlier versions.
+ *
+ * @example
+ * import { checkLocationPermission } from "tauri-plugin-macos-permissions-api";
+ *
+ * const authorized = await checkLocationPermission();
+ * console.log(authorized); // false
+ */
+export const checkLocationPermission = () => {
+  return checkDeprecated(
+    "checkLocationPermission",
+    "location",
+    COMMAND.CHECK_LOCATION_PERMISSION
+  );
+};
+
+/**
+ * Request location permission.
+ *
+ * Resolves once the user responded to the system prompt. Rejects with a `timedOut` {@link PermissionError} once
+ * the `requestTimeout` config elapses, without it the wait ends after two minutes.
+ *
+ * @returns `granted`, `denied`, `dismissed` if the prompt was not answered in time, or `promptUnavailable` if
+ * location services are disabled or restricted.
+ *
+ * @example
+ * import { requestLocationPermission } from "tauri-plugin-macos-permissions-api";
+ *
+ * const outcome = await requestLocationPermission();
+ * console.log(outcome); // "granted"
+ */
+export const requestLocationPermission = () => {
+  return invoke<RequestOutcome>(COMMAND.REQUEST_LOCATION_PERMISSION);
+};
+
+/**
+ * Open the Location Services pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openLocationSettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openLocationSettings();
+ */
+export const openLocationSettings = () => {
+  return invoke(COMMAND.OPEN_LOCATION_SETTINGS);
+};
+
+/**
+ * Get the current position from CoreLocation, requires location permission.
+ *
+ * @param timeout How long to wait for a position in milliseconds, defaults to `10000`.
+ * @returns The current position.
+ *
+ * @example
+ * import { getCurrentPosition } from "tauri-plugin-macos-permissions-api";
+ *
+ * const position = await getCurrentPosition();
+ * console.log(position.latitude); // 31.2304
+ */
+export const getCurrentPosition = (timeout?: number) => {
+  return invoke<Position>(COMMAND.GET_CURRENT_POSITION, { timeout });
+};
+
+/**
+ * Route `navigator.geolocation.getCurrentPosition` through CoreLocation.
+ *
+ * WKWebView's own geolocation prompt does not work in Tauri apps, this requests location permission
+ * natively and resolves with the position reported by CoreLocation instead. `watchPosition` is not covered,
+ * it is still left to WKWebView.
+ *
+ * @example
+ * import { installGeolocationBridge } from "tauri-plugin-macos-permissions-api";
+ *
+ * installGeolocationBridge();
+ *
+ * navigator.geolocation.getCurrentPosition((position) => {
+ *   console.log(position.coords.latitude); // 31.2304
+ * });
+ */
+export const installGeolocationBridge = () => {
+  const PERMISSION_DENIED = 1;
+  const POSITION_UNAVAILABLE = 2;
+  const TIMEOUT = 3;
+
+  navigator.geolocation.getCurrentPosition = async (
+    success,
+    error,
+    options
+  ) => {
+    const fail = (code: number, message: string) => {
+      error?.({
+        code,
+        message,
+        PERMISSION_DENIED,
+        POSITION_UNAVAILABLE,
+        TIMEOUT,
+      } as GeolocationPositionError);
+    };
+
+    try {
+      const { status } = await checkPermission("location");
+
+      if (
+        status !== "granted" &&
+        (await requestLocationPermission()) !== "granted"
+      ) {
+        return fail(PERMISSION_DENIED, "Location permission is not granted");
+      }
+
+      const { timestamp, ...coords } = await getCurrentPosition(
+        options?.timeout
+      );
+
+      success({
+        coords: { ...coords, heading: null, speed: null },
+        timestamp,
+      } as GeolocationPosition);
+    } catch (reason) {
+      if (!isPermissionError(reason)) {
+        return fail(POSITION_UNAVAILABLE, String(reason));
+      }
+
+      switch (reason.code) {
+        case "permissionDenied":
+          return fail(PERMISSION_DENIED, reason.message);
+        case "timedOut":
+          return fail(TIMEOUT, reason.message);
+        default:
+          return fail(POSITION_UNAVAILABLE, reason.message);
+      }
+    }
+  };
+};
+
+/**
+ * Check notification permission.
+ *
+ * @returns `true` if notification permission are granted, `false` otherwise.
+ *
+ * @deprecated Use `checkPermission("notifications")` instead, this still resolves as in earlier versions.
+ *
+ * @example
+ * import { checkNotificationPermission } from "tauri-plugin-macos-permissions-api";
+ *
+ * const authorized = await checkNotificationPermission();
+ * console.log(authorized); // false
+ */
+export const checkNotificationPermission = () => {
+  return checkDeprecated(
+    "checkNotificationPermission",
+    "notifications",
+    COMMAND.CHECK_NOTIFICATION_PERMISSION
+  );
+};
+
+/**
+ * Request notification permission.
+ *
+ * Rejects with a `timedOut` {@link PermissionError} once the `requestTimeout` config elapses.
+ *
+ * @returns `granted` if the user allowed notifications, `denied` otherwise.
+ *
+ * @example
+ * import { requestNotificationPermission } from "tauri-plugin-macos-permissions-api";
+ *
+ * const outcome = await requestNotificationPermission();
+ * console.log(outcome); // "granted"
+ */
+export const requestNotificationPermission = () => {
+  return invoke<RequestOutcome>(COMMAND.REQUEST_NOTIFICATION_PERMISSION);
+};
+
+/**
+ * Open the Notifications pane of System Settings, without showing a prompt.
+ *
+ * @example
+ * import { openNotificationSettings } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openNotificationSettings();
+ */
+export const openNotificationSettings = () => {
+  return invoke(COMMAND.OPEN_NOTIFICATION_SETTINGS);
+};
+
+/**
+ * Back the web `Notification` permission API with the native notification authorization.
+ *
+ * `Notification.permission` reflects the native status and `Notification.requestPermission()` shows the native
+ * prompt, so the webview and the app never disagree about whether notifications are allowed. The status is kept
+ * current through `subscribePermissionUpdates`, e.g. when notifications are turned off in System Settings.
+ *
+ * @param interval The polling interval of the subscription in milliseconds.
+ *
+ * @example
+ * import { installNotificationBridge } from "tauri-plugin-macos-permissions-api";
+ *
+ * await installNotificationBridge();
+ *
+ * const permission = await Notification.requestPermission();
+ * console.log(permission); // "granted"
+ */
+export const installNotificationBridge = async (interval?: number) => {
+  const toPermission = (status: PermissionStatus): NotificationPermission => {
+    if (status === "granted") return "granted";
+
+    if (status === "denied" || status === "restricted") return "denied";
+
+    return "default";
+  };
+
+  let permission = toPermission(
+    (await checkPermission("notifications")).status
+  );
+
+  Object.defineProperty(Notification, "permission", {
+    configurable: true,
+    get: () => permission,
+  });
+
+  await subscribePermissionUpdates(
+    (update) => {
+      const changes =
+        update.type === "snapshot"
+          ? update.statuses
+          : update.type === "batch"
+            ? update.changes
+            : [update];
+
+      for (const { kind, status } of changes) {
+        if (kind === "notifications") permission = toPermission(status);
+      }
+    },
+    ["notifications"],
+    interval
+  );
+
+  Notification.requestPermission = async (callback) => {
+    if (permission === "default") {
+      await requestNotificationPermission();
+
+      permission = toPermission(
+        (await checkPermission("notifications")).status
+      );
+    }
+
+    callback?.(permission);
+
+    return permission;
+  };
+};
+
+/**
+ * List the connected capture devices, requires the matching microphone or camera permission.
+ *
+ * @param mediaType `audio` for microphones, `video` for cameras.
+ * @returns The connected capture devices.
+ *
+ * @example
+ * import { listCaptureDevices } from "tauri-plugin-macos-permissions-api";
+ *
+ * const devices = await listCaptureDevices("audio");
+ * console.log(devices); // [{ id: "BuiltInMicrophoneDevice", name: "MacBook Pro Microphone", modelId: "..." }]
+ */
+export const listCaptureDevices = (mediaType: MediaType) => {
+  return invoke<CaptureDevice[]>(COMMAND.LIST_CAPTURE_DEVICES, { mediaType });
+};
+
+/**
+ * Verify that microphone permission works by capturing audio for one second.
+ *
+ * Catches grants that exist in System Settings while capture still fails or is silent, e.g. after an OS update.
+ *
+ * @returns `granted` if audio was captured, `ineffective` with a reason if not, `denied` without permission.
+ *
+ * @example
+ * import { verifyMicrophoneAccess } from "tauri-plugin-macos-permissions-api";
+ *
+ * const verification = await verifyMicrophoneAccess();
+ * console.log(verification); // { status: "ineffective", reason: "Only silence was captured, ..." }
+ */
+export const verifyMicrophoneAccess = () => {
+  return invoke<Verification>(COMMAND.VERIFY_MICROPHONE_ACCESS);
+};
+
+/**
+ * Verify that screen recording permission works by capturing a frame and reading window titles.
+ *
+ * The permission is reported as granted right away, while capture keeps returning black frames until the app is relaunched.
+ *
+ * @returns `granted` if the screen content is visible, `ineffective` with a reason if not, `denied` without permission.
+ *
+ * @example
+ * import { verifyScreenRecordingAccess } from "tauri-plugin-macos-permissions-api";
+ *
+ * const verification = await verifyScreenRecordingAccess();
+ * console.log(verification); // { status: "ineffective", reason: "The captured frame is black, ..." }
+ */
+export const verifyScreenRecordingAccess = () => {
+  return invoke<Verification>(COMMAND.VERIFY_SCREEN_RECORDING_ACCESS);
+};
+
+/**
+ * Verify that accessibility permission works by reading the title of the focused app.
+ *
+ * @returns `granted` if the query succeeded, `ineffective` with a reason if the trust is listed but rejected,
+ * `denied` without permission.
+ *
+ * @example
+ * import { verifyAccessibilityAccess } from "tauri-plugin-macos-permissions-api";
+ *
+ * const verification = await verifyAccessibilityAccess();
+ * console.log(verification); // { status: "granted" }
+ */
+export const verifyAccessibilityAccess = () => {
+  return invoke<Verification>(COMMAND.VERIFY_ACCESSIBILITY_ACCESS);
+};
+
+/**
+ * Check everything a global hotkey or key listener needs to install a keyboard event tap.
+ *
+ * @returns Whether the tap can be installed, and which permissions are missing.
+ *
+ * @example
+ * import { checkEventTapReadiness } from "tauri-plugin-macos-permissions-api";
+ *
+ * const readiness = await checkEventTapReadiness();
+ * console.log(readiness.missing); // ["inputMonitoring"]
+ */
+export const checkEventTapReadiness = () => {
+  return invoke<EventTapReadiness>(COMMAND.CHECK_EVENT_TAP_READINESS);
+};
+
+/**
+ * Check all permissions of a predefined profile.
+ *
+ * - `screen-recorder`: screen recording and microphone.
+ * - `clipboard-manager`: accessibility.
+ * - `automation-tool`: accessibility, input monitoring and screen recording.
+ * - `meeting-app`: microphone, camera, screen recording and notifications.
+ *
+ * @param profile The profile to check.
+ * @returns Whether the profile is ready, with the granted and missing permissions.
+ *
+ * @example
+ * import { checkProfile } from "tauri-plugin-macos-permissions-api";
+ *
+ * const readiness = await checkProfile("meeting-app");
+ * console.log(readiness.missing); // ["camera", "screenRecording"]
+ */
+export const checkProfile = (profile: Profile) => {
+  return invoke<ProfileReadiness>(COMMAND.CHECK_PROFILE, { profile });
+};
+
+/**
+ * Check whether the app is listed in the Privacy & Security pane of a permission, granted or denied.
+ *
+ * Tells whether the user has already been asked. Accessibility, full disk access and screen recording
+ * only report this once granted, or when the TCC database is readable with full disk access.
+ *
+ * @param kind The permission to check.
+ * @returns `true` if the app is listed, `false` if not, `null` if it cannot be determined.
+ *
+ * @example
+ * import { hasBeenRequested } from "tauri-plugin-macos-permissions-api";
+ *
+ * const requested = await hasBeenRequested("camera");
+ * console.log(requested); // false
+ */
+export const hasBeenRequested = (kind: PermissionKind) => {
+  return invoke<boolean | null>(COMMAND.HAS_BEEN_REQUESTED, { kind });
+};
+
+/**
+ * Get when the user last decided on a permission, read from the TCC databases.
+ *
+ * Lets apps implement "we asked you 30 days ago" from the system's own records. Only available with
+ * full disk access, which the TCC databases require, and not in App Store builds.
+ *
+ * @param kind The permission to look up.
+ * @returns When the prompt was answered, the decision last changed and reminded of, `null` without a record.
+ *
+ * @example
+ * import { getPromptHistory } from "tauri-plugin-macos-permissions-api";
+ *
+ * const history = await getPromptHistory("screenRecording");
+ * console.log(history?.lastPrompted); // 1718000000000
+ */
+export const getPromptHistory = (kind: PermissionKind) => {
+  return invoke<PromptHistory | null>(COMMAND.GET_PROMPT_HISTORY, { kind });
+};
+
+/**
+ * Generate a PPPC configuration profile pre-approving the given permissions, for IT admins deploying the app with MDM.
+ *
+ * Uses the bundle identifier and the designated requirement of the running app, which has to be signed.
+ * Only accessibility and full disk access can be granted by a profile, screen recording and input monitoring
+ * can only be made grantable by standard users, the other permissions are left out.
+ *
+ * @param permissions The permissions the app needs.
+ * @returns The `.mobileconfig` property list.
+ *
+ * @example
+ * import { generatePppcProfile } from "tauri-plugin-macos-permissions-api";
+ *
+ * const profile = await generatePppcProfile(["accessibility", "fullDiskAccess"]);
+ * console.log(profile); // <?xml version="1.0" encoding="UTF-8"?>...
+ */
+export const generatePppcProfile = (permissions: PermissionKind[]) => {
+  return invoke<string>(COMMAND.GENERATE_PPPC_PROFILE, { permissions });
+};
+
+/**
+ * Check whether the app runs translocated or quarantined.
+ *
+ * Apps opened right from Downloads run from a randomized read-only path until the user moves them,
+ * accessibility and full disk access grants are then lost on the next launch.
+ *
+ * @returns The bundle path, and whether it is translocated or quarantined.
+ *
+ * @example
+ * import { checkAppLocation } from "tauri-plugin-macos-permissions-api";
+ *
+ * const location = await checkAppLocation();
+ * console.log(location.translocated); // false
+ */
+export const checkAppLocation = () => {
+  return invoke<AppLocation>(COMMAND.CHECK_APP_LOCATION);
+};
+
+/**
+ * Get how the running app is identified, to debug grants remembered for another copy of it.
+ *
+ * TCC keys grants by bundle identifier and code signature, a rebuilt, re-signed or bare dev binary
+ * is a different app to it.
+ *
+ * @returns The executable path, bundle identifier, signature, and whether the app is bundled.
+ *
+ * @example
+ * import { getAppIdentity } from "tauri-plugin-macos-permissions-api";
+ *
+ * const identity = await getAppIdentity();
+ * console.log(identity.bundleId); // "com.example.app"
+ */
+export const getAppIdentity = () => {
+  return invoke<AppIdentity>(COMMAND.GET_APP_IDENTITY);
+};
+
+/**
+ * Find the other copies of the app, e.g. one in `/Applications` and one left in `~/Downloads`.
+ *
+ * Users often grant the permissions to one copy and launch another, which TCC treats as a
+ * different app unless it is signed the same.
+ *
+ * @returns Every copy with its version and whether it shares the grants of the running one, and the copy that likely holds the grants instead.
+ *
+ * @example
+ * import { findAppCopies } from "tauri-plugin-macos-permissions-api";
+ *
+ * const { likelyGranted } = await findAppCopies();
+ * console.log(likelyGranted); // "/Applications/Example.app"
+ */
+export const findAppCopies = () => {
+  return invoke<AppCopies>(COMMAND.FIND_APP_COPIES);
+};
+
+/**
+ * Get the process TCC attributes the permissions of the app to.
+ *
+ * Launched from a terminal or an IDE, e.g. with `tauri dev`, the app is not responsible for itself: its prompts name the terminal, and the grants in System Settings are the terminal's.
+ *
+ * @returns The responsible process, whether it is the app itself, and what to do otherwise. `null` if it cannot be determined, as in App Store builds.
+ *
+ * @example
+ * import { getResponsibleProcess } from "tauri-plugin-macos-permissions-api";
+ *
+ * const responsible = await getResponsibleProcess();
+ * console.log(responsible?.bundleId); // "com.apple.Terminal"
+ */
+export const getResponsibleProcess = () => {
+  return invoke<ResponsibleProcess | null>(COMMAND.GET_RESPONSIBLE_PROCESS);
+};
+
+/**
+ * Check whether the user is connected remotely, where permission prompts may not be presentable.
+ *
+ * Prompts only appear on the Mac's own display, requests made over SSH, Screen Sharing or
+ * Remote Management resolve with `remoteSession` instead of waiting for them.
+ *
+ * @returns How the user is connected with guidance to show them, `null` for a local session.
+ *
+ * @example
+ * import { checkRemoteSession } from "tauri-plugin-macos-permissions-api";
+ *
+ * const session = await checkRemoteSession();
+ * console.log(session?.kind); // "screenSharing"
+ */
+export const checkRemoteSession = () => {
+  return invoke<RemoteSession | null>(COMMAND.CHECK_REMOTE_SESSION);
+};
+
+/**
+ * Check whether the app runs in a virtual machine.
+ *
+ * Cameras, microphones and screen recording behave differently in VMs, as on VM-based CI runners.
+ *
+ * @returns `true` if the Mac is a virtual machine, `false` otherwise.
+ *
+ * @example
+ * import { isVirtualMachine } from "tauri-plugin-macos-permissions-api";
+ *
+ * const virtualMachine = await isVirtualMachine();
+ * console.log(virtualMachine); // false
+ */
+export const isVirtualMachine = () => {
+  return invoke<boolean>(COMMAND.IS_VIRTUAL_MACHINE);
+};
+
+/**
+ * Check whether System Integrity Protection is enabled.
+ *
+ * With SIP turned off, as on some test and enterprise machines, the TCC database can be edited
+ * by other processes and grants may change without going through System Settings.
+ *
+ * @returns `true` if no protection of SIP is turned off, `false` otherwise, `null` if it cannot be
+ * checked, as with the `app-store` feature.
+ *
+ * @example
+ * import { isSipEnabled } from "tauri-plugin-macos-permissions-api";
+ *
+ * const enabled = await isSipEnabled();
+ * console.log(enabled); // true
+ */
+export const isSipEnabled = () => {
+  return invoke<boolean | null>(COMMAND.IS_SIP_ENABLED);
+};
+
+/**
+ * Get the version and build configuration of the plugin, for bug reports and remote diagnostics.
+ *
+ * @returns The plugin version, the SDK and deployment target it was built with, its enabled features and the running macOS version.
+ *
+ * @example
+ * import { getPluginInfo } from "tauri-plugin-macos-permissions-api";
+ *
+ * const info = await getPluginInfo();
+ * console.log(info.features); // ["app-store"]
+ */
+export const getPluginInfo = () => {
+  return invoke<PluginInfo>(COMMAND.GET_PLUGIN_INFO);
+};
+
+/**
+ * Get the counters of the current session, e.g. to spot a frontend polling too often or to measure onboarding.
+ *
+ * System Settings opened with the `open*Settings` methods is not counted, the app knows about those itself.
+ *
+ * @returns How many checks were performed, prompts shown, System Settings opened and grants observed, and how long the session lasts.
+ *
+ * @example
+ * import { getPermissionStats } from "tauri-plugin-macos-permissions-api";
+ *
+ * const { checks, elapsed } = await getPermissionStats();
+ * console.log(checks / (elapsed / 1000)); // 0.5
+ */
+export const getPermissionStats = () => {
+  return invoke<PermissionStats>(COMMAND.GET_PERMISSION_STATS);
+};
+
+/**
+ * Read paths, reporting which access is missing for the ones the app cannot read.
+ *
+ * The `fileDrop` config runs the same on dropped files. Reading paths in the Desktop, Documents or Downloads folder, iCloud Drive or on another volume shows the Files & Folders prompt if the user was not asked yet, so call it when the app is about to use the paths.
+ *
+ * @param paths The files or folders to read.
+ * @returns Each path with `readable`, the missing access, or `missing` if it does not exist.
+ *
+ * @example
+ * import { requestPathAccess } from "tauri-plugin-macos-permissions-api";
+ *
+ * const [entry] = await requestPathAccess(["/Users/me/Library/Mail"]);
+ * console.log(entry.access); // "fullDiskAccess"
+ */
+export const requestPathAccess = (paths: string[]) => {
+  return invoke<PathAccessEntry[]>(COMMAND.REQUEST_PATH_ACCESS, { paths });
+};
+
+/**
+ * Check whether global shortcuts can be registered.
+ *
+ * Hotkeys registered by `tauri-plugin-global-shortcut` only need a GUI session. Shortcuts observed
+ * with an event tap, such as modifier-only ones, also need accessibility and input monitoring.
+ *
+ * @param eventTap Whether the shortcuts are observed with an event tap, defaults to `false`.
+ * @returns Whether the shortcuts can be registered, and the first missing prerequisite.
+ *
+ * @example
+ * import { checkGlobalShortcutReadiness } from "tauri-plugin-macos-permissions-api";
+ *
+ * const readiness = await checkGlobalShortcutReadiness(true);
+ * console.log(readiness); // { ready: false, blocker: "inputMonitoringMissing" }
+ */
+export const checkGlobalShortcutReadiness = (eventTap?: boolean) => {
+  return invoke<ShortcutReadiness>(COMMAND.CHECK_GLOBAL_SHORTCUT_READINESS, {
+    eventTap,
+  });
+};
+
+/**
+ * Call `handler` once global shortcuts can be registered, right away if they already can.
+ *
+ * @param handler Called once the prerequisites are satisfied.
+ * @param eventTap Whether the shortcuts are observed with an event tap, defaults to `false`.
+ * @param interval The polling interval in milliseconds.
+ * @returns A function to stop waiting.
+ *
+ * @example
+ * import { onGlobalShortcutReady } from "tauri-plugin-macos-permissions-api";
+ * import { register } from "@tauri-apps/plugin-global-shortcut";
+ *
+ * await onGlobalShortcutReady(() => register("CommandOrControl+Shift+K", toggle), true);
+ */
+export const onGlobalShortcutReady = async (
+  handler: () => void,
+  eventTap?: boolean,
+  interval?: number
+) => {
+  let id: number | undefined;
+  let done = false;
+
+  // The event can arrive before the watcher id when the shortcuts are already ready.
+  const early = new Set<number>();
+
+  const ready = () => {
+    if (done) return;
+
+    done = true;
+    unlisten();
+    handler();
+  };
+
+  const unlisten = await listen<number>(EVENT.GLOBAL_SHORTCUT_READY, (event) => {
+    if (id === undefined) {
+      early.add(event.payload);
+    } else if (event.payload === id) {
+      ready();
+    }
+  });
+
+  id = await invoke<number>(COMMAND.WATCH_GLOBAL_SHORTCUT_READINESS, {
+    eventTap,
+    interval,
+  });
+
+  if (early.has(id)) ready();
+
+  return () => {
+    unlisten();
+
+    if (id !== undefined) unwatch(id);
+  };
+};
+
+/**
+ * Get a compact overview of several permissions, e.g. to show in a tray menu.
+ *
+ * @param kinds The permissions to include, defaults to the `permissions` config.
+ * @returns The status of each permission, whether all are granted and the missing ones.
+ *
+ * @example
+ * import { getStatusSummary } from "tauri-plugin-macos-permissions-api";
+ *
+ * const summary = await getStatusSummary();
+ * console.log(summary.ok, summary.missing); // false ["screenRecording"]
+ */
+export const getStatusSummary = (kinds?: PermissionKind[]) => {
+  return invoke<StatusSummary>(COMMAND.GET_STATUS_SUMMARY, { kinds });
+};
+
+/**
+ * Open the built-in onboarding window, listing the permissions with their live status.
+ *
+ * @param kinds The permissions to list, defaults to the `permissions` config.
+ *
+ * @example
+ * import { openOnboarding } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openOnboarding(["accessibility", "screenRecording"]);
+ */
+export const openOnboarding = (kinds?: PermissionKind[]) => {
+  return invoke(COMMAND.OPEN_ONBOARDING, { kinds });
+};
+
+/**
+ * Listen for the onboarding window being closed.
+ *
+ * @returns A function to stop listening.
+ *
+ * @example
+ * import { onOnboardingFinished } from "tauri-plugin-macos-permissions-api";
+ *
+ * const unlisten = await onOnboardingFinished((payload) => {
+ *   console.log(payload.completed, payload.missing); // false ["screenRecording"]
+ * });
+ */
+export const onOnboardingFinished = (
+  handler: (payload: OnboardingFinished) => void
+) => {
+  return listen<OnboardingFinished>(EVENT.ONBOARDING_FINISHED, (event) => {
+    handler(event.payload);
+  });
+};
+
+/**
+ * Never remind the user about a permission again, e.g. when they pick "Don't ask again".
+ *
+ * @param kind The permission to stop reminding about.
+ *
+ * @example
+ * import { dismissReminder } from "tauri-plugin-macos-permissions-api";
+ *
+ * await dismissReminder("screenRecording");
+ */
+export const dismissReminder = (kind: PermissionKind) => {
+  return invoke(COMMAND.DISMISS_REMINDER, { kind });
+};
+
+/**
+ * Forget the reminders of a permission, or of every permission, so they start over.
+ *
+ * @param kind The permission to reset, defaults to all of them.
+ *
+ * @example
+ * import { resetReminders } from "tauri-plugin-macos-permissions-api";
+ *
+ * await resetReminders();
+ */
+export const resetReminders = (kind?: PermissionKind) => {
+  return invoke(COMMAND.RESET_REMINDERS, { kind });
+};
+
+/**
+ * Listen for reminders about missing permissions, scheduled by the `reminders` config.
+ *
+ * @returns A function to stop listening.
+ *
+ * @example
+ * import { onReminder } from "tauri-plugin-macos-permissions-api";
+ *
+ * const unlisten = await onReminder((payload) => {
+ *   console.log(payload.kind, payload.count); // "screenRecording" 2
+ * });
+ */
+export const onReminder = (handler: (payload: Reminder) => void) => {
+  return listen<Reminder>(EVENT.REMINDER, (event) => {
+    handler(event.payload);
+  });
+};
+
+/**
+ * Get the macOS major upgrade detected on this launch, with the re-verified permissions.
+ *
+ * Upgrades regularly reset or re-scope grants. The same payload is emitted to `onOsUpgraded`,
+ * which may fire before the frontend listens.
+ *
+ * @returns The previous and running version with a status summary, `null` without an upgrade.
+ *
+ * @example
+ * import { getOsUpgrade } from "tauri-plugin-macos-permissions-api";
+ *
+ * const upgraded = await getOsUpgrade();
+ * console.log(upgraded?.summary.missing); // ["screenRecording"]
+ */
+export const getOsUpgrade = () => {
+  return invoke<OsUpgraded | null>(COMMAND.GET_OS_UPGRADE);
+};
+
+/**
+ * Listen for the permissions re-verified on the first launch after a macOS major upgrade.
+ *
+ * @returns A function to stop listening.
+ *
+ * @example
+ * import { onOsUpgraded } from "tauri-plugin-macos-permissions-api";
+ *
+ * const unlisten = await onOsUpgraded((payload) => {
+ *   console.log(payload.previousVersion, payload.version); // "14.6" "15.1"
+ * });
+ */
+export const onOsUpgraded = (handler: (payload: OsUpgraded) => void) => {
+  return listen<OsUpgraded>(EVENT.OS_UPGRADED, (event) => {
+    handler(event.payload);
+  });
+};
+
+/**
+ * Listen for permissions granted after a `request*` method opened System Settings.
+ *
+ * @returns A function to stop listening.
+ *
+ * @example
+ * import { onGrantCompleted } from "tauri-plugin-macos-permissions-api";
+ *
+ * const unlisten = await onGrantCompleted((payload) => {
+ *   console.log(payload.kind, payload.refocused); // "fullDiskAccess" true
+ * });
+ */
+export const onGrantCompleted = (handler: (payload: GrantCompleted) => void) => {
+  return listen<GrantCompleted>(EVENT.GRANT_COMPLETED, (event) => {
+    handler(event.payload);
+  });
+};
+
+/**
+ * Listen for the access of dropped files being checked, emitted with the `fileDrop` config.
+ *
+ * @returns A function to stop listening.
+ *
+ * @example
+ * import { onFileDropResolved } from "tauri-plugin-macos-permissions-api";
+ *
+ * const unlisten = await onFileDropResolved(({ paths, ready }) => {
+ *   console.log(ready, paths[0].access); // false "filesAndFolders"
+ * });
+ */
+export const onFileDropResolved = (
+  handler: (payload: FileDropResolved) => void
+) => {
+  return listen<FileDropResolved>(EVENT.FILE_DROP_RESOLVED, (event) => {
+    handler(event.payload);
+  });
+};
+
+/**
+ * Check a privacy service the plugin does not model, by its raw TCC identifier.
+ *
+ * @param service The TCC service, e.g. `kTCCServiceCalendar`.
+ * @returns The status of the service, `unsupported` where it cannot be queried.
+ *
+ * @example
+ * import { checkTccService } from "tauri-plugin-macos-permissions-api";
+ *
+ * const status = await checkTccService("kTCCServiceCalendar");
+ * console.log(status); // "notDetermined"
+ */
+export const checkTccService = (service: string) => {
+  return invoke<PermissionStatus>(COMMAND.CHECK_TCC_SERVICE, { service });
+};
+
+/**
+ * Open the Privacy & Security pane of a privacy service the plugin does not model, by its raw TCC identifier.
+ *
+ * @param service The TCC service, e.g. `kTCCServiceCalendar`.
+ *
+ * @example
+ * import { openSettingsForService } from "tauri-plugin-macos-permissions-api";
+ *
+ * await openSettingsForService("kTCCServiceCalendar");
+ */
+export const openSettingsForService = (service: string) => {
+  return invoke(COMMAND.OPEN_SETTINGS_FOR_SERVICE, { service });
+};
+
+/**
+ * Open the System Settings pane of a permission and wait until the user is done there.
+ *
+ * Resolves once System Settings is closed or the status of the permission changes, so it can be checked exactly once.
+ * Rejects with a `cancelled` {@link PermissionError} once `signal` is aborted.
+ *
+ * @param kind The permission whose pane to open.
+ * @param options.signal Stops waiting when aborted, e.g. when the dialog waiting for it is closed.
+ * @returns The status of the permission when the user is done.
+ *
+ * @example
+ * import { openSettingsAndWait } from "tauri-plugin-macos-permissions-api";
+ *
+ * const controller = new AbortController();
+ *
+ * const status = await openSettingsAndWait("fullDiskAccess", { signal: controller.signal });
+ * console.log(status); // "granted"
+ */
+export const openSettingsAndWait = (
+  kind: PermissionKind,
+  options?: { signal?: AbortSignal }
+) => {
+  const cancelId = cancellation(options?.signal);
+
+  return invoke<PermissionStatus>(COMMAND.OPEN_SETTINGS_AND_WAIT, {
+    kind,
+    cancelId,
+  });
+};
+
+/**
+ * Cancel a wait started with a cancel id, see the `signal` option of {@link openSettingsAndWait}.
+ *
+ * @returns `true` if the wait was cancelled, `false` if it is not running.
+ *
+ * @example
+ * import { cancel } from "tauri-plugin-macos-permissions-api";
+ *
+ * const cancelled = await cancel(id);
+ * console.log(cancelled); // true
+ */
+export const cancel = (id: number) => {
+  return invoke<boolean>(COMMAND.CANCEL, { id });
+};
+
+/**
+ * Check a permission, request it only if it is missing, and optionally wait until it is granted.
+ *
+ * The request follows the `requestStrategies` config like the `request*` methods, and rejects with a
+ * `timedOut` {@link PermissionError} once the `requestTimeout` config elapses. Without it, waiting for
+ * the grant ends after five minutes, resolving with `granted: false`.
+ *
+ * @param kind The permission to ensure.
+ * @param options Whether to request the permission, and to wait for the grant after System Settings was opened. Defaults to requesting without waiting.
+ * @returns Whether the permission is granted, its status, and the outcome of the request if one was made.
+ *
+ * @example
+ * import { ensurePermission } from "tauri-plugin-macos-permissions-api";
+ *
+ * const { granted, outcome } = await ensurePermission("fullDiskAccess", { wait: true });
+ * console.log(granted, outcome); // true "openedSettings"
+ */
+export const ensurePermission = (
+  kind: PermissionKind,
+  options?: EnsurePermissionOptions
+) => {
+  const { signal, ...rest } = options ?? {};
+  const cancelId = cancellation(signal);
+
+  return invoke<EnsuredPermission>(COMMAND.ENSURE_PERMISSION, {
+    kind,
+    options: rest,
+    cancelId,
+  });
+};
+
+/** Pick a cancel id cancelling the wait once `signal` is aborted. */
+const cancellation = (signal?: AbortSignal) => {
+  if (!signal) return;
+
+  const id = crypto.getRandomValues(new Uint32Array(1))[0];
+
+  signal.addEventListener("abort", () => cancel(id), { once: true });
+
+  return id;
+};
+
+/**
+ * Watch a single permission, emitting an event whenever it changes.
+ *
+ * @param kind The permission to watch.
+ * @param interval The polling interval in milliseconds, defaults to `1000`.
+ * @param targets The labels of the windows or webviews receiving the events, defaults to the `eventTargets` config.
+ * @returns The watcher id, pass it to `unwatch` to stop watching.
+ *
+ * @example
+ * import { watchPermission, onPermissionChanged } from "tauri-plugin-macos-permissions-api";
+ *
+ * const id = await watchPermission("accessibility");
+ *
+ * await onPermissionChanged((payload) => {
+ *   if (payload.id !== id) return;
+ *
+ *   console.log(payload.authorized); // true
+ * });
+ */
+export const watchPermission = (
+  kind: PermissionKind,
+  interval?: number,
+  targets?: string[],
+) => {
+  return invoke<number>(COMMAND.WATCH_PERMISSION, { kind, interval, targets });
+};
+
+/**
+ * Stream permission statuses over a channel, scaling better than events with many windows.
+ *
+ * Receives a snapshot of every subscribed permission first, then each change.
+ *
+ * @param handler Called with every update.
+ * @param kinds The permissions to subscribe to, defaults to all of them.
+ * @param interval The polling interval in milliseconds.
+ * @returns The subscription id, pass it to `unwatch` to stop the updates.
+ *
+ * @example
+ * import { subscribePermissionUpdates } from "tauri-plugin-macos-permissions-api";
+ *
+ * const id = await subscribePermissionUpdates((update) => {
+ *   console.log(update); // { type: "changed", kind: "camera", status: "granted", previous: "notDetermined" }
+ * }, ["camera", "microphone"]);
+ */
+export const subscribePermissionUpdates = (
+  handler: (update: PermissionUpdate) => void,
+  kinds?: PermissionKind[],
+  interval?: number
+) => {
+  const channel = new Channel<PermissionUpdate>();
+
+  channel.onmessage = handler;
+
+  return invoke<number>(COMMAND.SUBSCRIBE_PERMISSION_UPDATES, {
+    channel,
+    kinds,
+    interval,
+  });
+};
+
+/**
+ * Stop a permission watcher or subscription.
+ *
+ * @returns `true` if the watcher was stopped, `false` if it does not exist.
+ *
+ * @example
+ * import { unwatch } from "tauri-plugin-macos-permissions-api";
+ *
+ * await unwatch(id);
+ */
+export const unwatch = (id: number) => {
+  return invoke<boolean>(COMMAND.UNWATCH, { id });
+};
+
+/**
+ * Listen for changes detected by the permission watchers.
+ *
+ * The changes batched with the `coalesceWindow` config are passed one by one.
+ *
+ * @returns A function to stop listening.
+ *
+ * @example
+ * import { onPermissionChanged } from "tauri-plugin-macos-permissions-api";
+ *
+ * const unlisten = await onPermissionChanged((payload) => {
+ *   console.log(payload.kind, payload.authorized); // "accessibility" true
+ * });
+ */
+export const onPermissionChanged = (
+  handler: (payload: PermissionChanged) => void
+) => {
+  const unlisteners = Promise.all([
+    listen<PermissionChanged>(EVENT.PERMISSION_CHANGED, (event) => {
+      handler(event.payload);
+    }),
+    listen<PermissionsChanged>(EVENT.PERMISSIONS_CHANGED, (event) => {
+      event.payload.changes.forEach(handler);
+    }),
+  ]);
+
+  return unlisteners.then((unlisten) => () => {
+    unlisten.forEach((unlisten) => unlisten());
+  });
+};
+
+/**
+ * Listen for the changes the permission watchers detected within the `coalesceWindow` config, as one batch.
+ *
+ * @returns A function to stop listening.
+ *
+ * @example
+ * import { onPermissionsChanged } from "tauri-plugin-macos-permissions-api";
+ *
+ * const unlisten = await onPermissionsChanged((payload) => {
+ *   console.log(payload.changes.length); // 3
+ * });
+ */
+export const onPermissionsChanged = (
+  handler: (payload: PermissionsChanged) => void
+) => {
+  return listen<PermissionsChanged>(EVENT.PERMISSIONS_CHANGED, (event) => {
+    handler(event.payload);
+  });
+};
+
+/**
+ * Check the status of a permission.
+ *
+ * @param kind The permission to check.
+ * @param details Whether to include the raw values the status was derived from, defaults to `false`.
+ * @returns The status of the permission, with the raw values if requested. For microphone and camera it also reports
+ * whether a capture device is connected, to tell "denied" from "unplugged".
+ *
+ * @example
+ * import { checkPermission } from "tauri-plugin-macos-permissions-api";
+ *
+ * const state = await checkPermission("camera", true);
+ * console.log(state); // { kind: "camera", status: "notDetermined", deviceConnected: true, managed: false, details: { authorizationStatus: 0 } }
+ */
+export const checkPermission = (kind: PermissionKind, details?: boolean) => {
+  return invoke<PermissionState>(COMMAND.CHECK_PERMISSION, { kind, details });
+};
+
+/**
+ * Gate `navigator.mediaDevices.getDisplayMedia` of WebKit on the screen recording permission.
+ *
+ * Requests screen recording permission first when it is not granted yet, and rejects with a `NotAllowedError`
+ * instead of returning a black stream while the permission is missing. The screen is still picked and captured by
+ * WebKit, not through ScreenCaptureKit or its `SCContentSharingPicker`.
+ *
+ * @param options The options passed to `getDisplayMedia`.
+ * @returns The captured media stream.
+ *
+ * @example
+ * import { getDisplayMedia } from "tauri-plugin-macos-permissions-api";
+ *
+ * const stream = await getDisplayMedia({ video: true });
+ * video.srcObject = stream;
+ */
+export const getDisplayMedia = async (options?: DisplayMediaStreamOptions) => {
+  if (!(await checkScreenRecordingPermission())) {
+    const outcome = await requestScreenRecordingPermission();
+
+    if (outcome !== "granted") {
+      throw new DOMException(
+        "Screen recording permission is not granted",
+        "NotAllowedError"
+      );
+    }
+  }
+
+  return navigator.mediaDevices.getDisplayMedia(options);
+};
+
+const WEB_PERMISSION_KIND: Record<string, PermissionKind> = {
+  camera: "camera",
+  microphone: "microphone",
+  geolocation: "location",
+  notifications: "notifications",
+  "display-capture": "screenRecording",
+};
+
+type WebPermissionState = "granted" | "denied" | "prompt";
+
+const toWebPermissionState = (status: PermissionStatus): WebPermissionState => {
+  if (status === "granted") return "granted";
+
+  if (status === "denied" || status === "restricted") return "denied";
+
+  return "prompt";
+};
+
+class MacosPermissionStatus extends EventTarget {
+  onchange:
+    | ((this: globalThis.PermissionStatus, event: Event) => unknown)
+    | null = null;
+
+  constructor(public name: string, public state: WebPermissionState) {
+    super();
+  }
+
+  update(state: WebPermissionState) {
+    if (state === this.state) return;
+
+    this.state = state;
+
+    const event = new Event("change");
+
+    this.onchange?.call(this as unknown as globalThis.PermissionStatus, event);
+    this.dispatchEvent(event);
+  }
+}
+
+/**
+ * Make `navigator.permissions.query` reflect the macOS permission state.
+ *
+ * Queries for `camera`, `microphone`, `geolocation`, `notifications` and `display-capture` resolve with the
+ * system status, and fire `change` events while the permission is watched. Other names fall through to the webview.
+ *
+ * @param interval The polling interval of the watchers in milliseconds, defaults to `1000`.
+ *
+ * @example
+ * import { installPermissionsBridge } from "tauri-plugin-macos-permissions-api";
+ *
+ * installPermissionsBridge();
+ *
+ * const status = await navigator.permissions.query({ name: "camera" as PermissionName });
+ * console.log(status.state); // "prompt"
+ */
+export const installPermissionsBridge = (interval?: number) => {
+  const query = navigator.permissions.query.bind(navigator.permissions);
+  const watchers = new Map<PermissionKind, Promise<number>>();
+  const statuses = new Set<{
+    kind: PermissionKind;
+    status: MacosPermissionStatus;
+  }>();
+
+  onPermissionChanged((payload) => {
+    for (const { kind, status } of statuses) {
+      if (kind !== payload.kind) continue;
+
+      checkPermission(kind).then((state) => {
+        status.update(toWebPermissionState(state.status));
+      });
+    }
+  });
+
+  navigator.permissions.query = async (descriptor) => {
+    const kind = WEB_PERMISSION_KIND[descriptor.name];
+
+    if (!kind) return query(descriptor);
+
+    const state = await checkPermission(kind);
+    const status = new MacosPermissionStatus(
+      descriptor.name,
+      toWebPermissionState(state.status)
+    );
+
+    statuses.add({ kind, status });
+
+    if (!watchers.has(kind)) {
+      watchers.set(kind, watchPermission(kind, interval));
+    }
+
+    return status as unknown as globalThis.PermissionStatus;
+  };
+};
+
+/**
+ * Check whether a rejected invoke is a {@link PermissionError}, e.g. caused by a missing permission.
+ *
+ * @param error The rejection of `invoke`.
+ *
+ * @example
+ * import { isPermissionError } from "tauri-plugin-macos-permissions-api";
+ *
+ * try {
+ *   await invoke("start_recording");
+ * } catch (error) {
+ *   if (isPermissionError(error)) {
+ *     console.log(error.permission); // "microphone"
+ *   }
+ * }
+ */
+export const isPermissionError = (error: unknown): error is PermissionError => {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    PERMISSION_ERROR_CODES.includes(error.code as PermissionErrorCode)
+  );
 };
@@ -0,0 +1,47 @@
+import { useEffect, useState } from "react";
+
+import type { PermissionKind } from "./index";
+import { watchStatuses, type PermissionStatuses } from "./statuses";
+
+export type { PermissionStatuses };
+
+/**
+ * Live statuses of several permissions, updated whenever one of them changes.
+ *
+ * @param kinds The permissions to subscribe to.
+ * @param interval The polling interval in milliseconds, defaults to the `watchInterval` config.
+ * @returns The status of each permission, empty until the first snapshot arrives.
+ *
+ * @example
+ * import { usePermissions } from "tauri-plugin-macos-permissions-api/react";
+ *
+ * const statuses = usePermissions(["camera", "microphone"]);
+ * console.log(statuses.camera); // "granted"
+ */
+export const usePermissions = (kinds: PermissionKind[], interval?: number) => {
+  const [statuses, setStatuses] = useState<PermissionStatuses>({});
+
+  // Compared by value, so callers can pass a new array on every render.
+  const key = kinds.join(",");
+
+  useEffect(() => watchStatuses(kinds, interval, setStatuses), [key, interval]);
+
+  return statuses;
+};
+
+/**
+ * Live status of a permission, updated whenever it changes.
+ *
+ * @param kind The permission to subscribe to.
+ * @param interval The polling interval in milliseconds, defaults to the `watchInterval` config.
+ * @returns The status of the permission, `undefined` until it is known.
+ *
+ * @example
+ * import { usePermission } from "tauri-plugin-macos-permissions-api/react";
+ *
+ * const status = usePermission("screenRecording");
+ * console.log(status); // "denied"
+ */
+export const usePermission = (kind: PermissionKind, interval?: number) => {
+  return usePermissions([kind], interval)[kind];
+};
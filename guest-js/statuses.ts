@@ -0,0 +1,52 @@
+import {
+  subscribePermissionUpdates,
+  unwatch,
+  type PermissionKind,
+  type PermissionStatus,
+} from "./index";
+
+export type PermissionStatuses = Partial<Record<PermissionKind, PermissionStatus>>;
+
+/**
+ * Call `handler` with the statuses of `kinds`, first with a snapshot and then after every change.
+ *
+ * @returns A function to stop the subscription.
+ */
+export const watchStatuses = (
+  kinds: PermissionKind[],
+  interval: number | undefined,
+  handler: (statuses: PermissionStatuses) => void
+) => {
+  let active = true;
+  let statuses: PermissionStatuses = {};
+
+  const id = subscribePermissionUpdates(
+    (update) => {
+      if (!active) return;
+
+      if (update.type === "snapshot") {
+        statuses = Object.fromEntries(
+          update.statuses.map(({ kind, status }) => [kind, status])
+        );
+      } else {
+        const changes = update.type === "batch" ? update.changes : [update];
+
+        statuses = { ...statuses };
+
+        for (const { kind, status } of changes) {
+          statuses[kind] = status;
+        }
+      }
+
+      handler(statuses);
+    },
+    kinds,
+    interval
+  );
+
+  return () => {
+    active = false;
+
+    id.then(unwatch);
+  };
+};
@@ -0,0 +1,46 @@
+import { readable } from "svelte/store";
+
+import type { PermissionKind, PermissionStatus } from "./index";
+import { watchStatuses, type PermissionStatuses } from "./statuses";
+
+export type { PermissionStatuses };
+
+/**
+ * A readable store with the statuses of several permissions, updated whenever one of them changes.
+ *
+ * The subscription to the plugin only runs while the store has subscribers.
+ *
+ * @param kinds The permissions to subscribe to.
+ * @param interval The polling interval in milliseconds, defaults to the `watchInterval` config.
+ * @returns A store with the status of each permission, empty until the first snapshot arrives.
+ *
+ * @example
+ * import { permissionsStore } from "tauri-plugin-macos-permissions-api/svelte";
+ *
+ * const statuses = permissionsStore(["camera", "microphone"]);
+ * statuses.subscribe((value) => console.log(value.camera)); // "granted"
+ */
+export const permissionsStore = (kinds: PermissionKind[], interval?: number) => {
+  return readable<PermissionStatuses>({}, (set) => {
+    return watchStatuses(kinds, interval, set);
+  });
+};
+
+/**
+ * A readable store with the status of a permission, updated whenever it changes.
+ *
+ * @param kind The permission to subscribe to.
+ * @param interval The polling interval in milliseconds, defaults to the `watchInterval` config.
+ * @returns A store with the status of the permission, `undefined` until it is known.
+ *
+ * @example
+ * import { permissionStore } from "tauri-plugin-macos-permissions-api/svelte";
+ *
+ * const status = permissionStore("screenRecording");
+ * status.subscribe((value) => console.log(value)); // "denied"
+ */
+export const permissionStore = (kind: PermissionKind, interval?: number) => {
+  return readable<PermissionStatus | undefined>(undefined, (set) => {
+    return watchStatuses([kind], interval, (statuses) => set(statuses[kind]));
+  });
+};
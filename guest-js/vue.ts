@@ -0,0 +1,52 @@
+import { computed, onScopeDispose, shallowRef } from "vue";
+
+import type { PermissionKind } from "./index";
+import { watchStatuses, type PermissionStatuses } from "./statuses";
+
+export type { PermissionStatuses };
+
+/**
+ * Live statuses of several permissions, updated whenever one of them changes.
+ *
+ * Call it in `setup` or another effect scope, the subscription is stopped when the scope is disposed.
+ *
+ * @param kinds The permissions to subscribe to.
+ * @param interval The polling interval in milliseconds, defaults to the `watchInterval` config.
+ * @returns A readonly ref with the status of each permission, empty until the first snapshot arrives.
+ *
+ * @example
+ * import { usePermissions } from "tauri-plugin-macos-permissions-api/vue";
+ *
+ * const statuses = usePermissions(["camera", "microphone"]);
+ * console.log(statuses.value.camera); // "granted"
+ */
+export const usePermissions = (kinds: PermissionKind[], interval?: number) => {
+  const statuses = shallowRef<PermissionStatuses>({});
+
+  const stop = watchStatuses(kinds, interval, (value) => {
+    statuses.value = value;
+  });
+
+  onScopeDispose(stop);
+
+  return computed(() => statuses.value);
+};
+
+/**
+ * Live status of a permission, updated whenever it changes.
+ *
+ * @param kind The permission to subscribe to.
+ * @param interval The polling interval in milliseconds, defaults to the `watchInterval` config.
+ * @returns A readonly ref with the status of the permission, `undefined` until it is known.
+ *
+ * @example
+ * import { usePermission } from "tauri-plugin-macos-permissions-api/vue";
+ *
+ * const status = usePermission("screenRecording");
+ * console.log(status.value); // "denied"
+ */
+export const usePermission = (kind: PermissionKind, interval?: number) => {
+  const statuses = usePermissions([kind], interval);
+
+  return computed(() => statuses.value[kind]);
+};
@@ -0,0 +1,137 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::Parser, parse_macro_input, punctuated::Punctuated, FnArg, Ident, ItemFn, Pat, Token,
+    Type,
+};
+
+/// Argument types that give access to the `AppHandle`.
+const MANAGER_TYPES: [&str; 4] = ["AppHandle", "Window", "WebviewWindow", "Webview"];
+
+/// Check a permission before running an async Tauri command.
+///
+/// The command must take an `AppHandle`, `Window`, `WebviewWindow` or `Webview` argument and return a `Result`
+/// whose error converts from `tauri_plugin_macos_permissions::Error`, e.g. `String`.
+/// Pass `request` to show the permission request before giving up.
+///
+/// # Example
+/// ```ignore
+/// use tauri_plugin_macos_permissions::requires_permission;
+///
+/// #[tauri::command]
+/// #[requires_permission(Accessibility, request)]
+/// async fn paste(app_handle: tauri::AppHandle) -> Result<(), String> {
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn requires_permission(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match Punctuated::<Ident, Token![,]>::parse_terminated.parse(attr) {
+        Ok(args) => args,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let function = parse_macro_input!(item as ItemFn);
+
+    match expand(args, function) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(
+    args: Punctuated<Ident, Token![,]>,
+    function: ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut args = args.into_iter();
+
+    let kind = args.next().ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected a permission kind, e.g. `#[requires_permission(Accessibility)]`",
+        )
+    })?;
+
+    let request = match args.next() {
+        None => false,
+        Some(flag) if flag == "request" => true,
+        Some(flag) => return Err(syn::Error::new_spanned(flag, "expected `request`")),
+    };
+
+    if let Some(extra) = args.next() {
+        return Err(syn::Error::new_spanned(extra, "unexpected argument"));
+    }
+
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            function.sig.fn_token,
+            "`requires_permission` only supports async commands",
+        ));
+    }
+
+    let manager = function
+        .sig
+        .inputs
+        .iter()
+        .find_map(manager_argument)
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &function.sig,
+                "`requires_permission` needs an `AppHandle`, `Window`, `WebviewWindow` or `Webview` argument",
+            )
+        })?;
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            ::tauri_plugin_macos_permissions::require_permission(
+                #manager,
+                ::tauri_plugin_macos_permissions::PermissionKind::#kind,
+                #request,
+            )
+            .await?;
+
+            #block
+        }
+    })
+}
+
+/// Returns the expression borrowing the argument, if it is one of [`MANAGER_TYPES`].
+fn manager_argument(input: &FnArg) -> Option<proc_macro2::TokenStream> {
+    let FnArg::Typed(argument) = input else {
+        return None;
+    };
+
+    let Pat::Ident(pat) = &*argument.pat else {
+        return None;
+    };
+
+    let ident = &pat.ident;
+
+    let (ty, is_reference) = match &*argument.ty {
+        Type::Reference(reference) => (&*reference.elem, true),
+        ty => (ty, false),
+    };
+
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if !MANAGER_TYPES.iter().any(|name| segment.ident == name) {
+        return None;
+    }
+
+    Some(if is_reference {
+        quote!(#ident)
+    } else {
+        quote!(&#ident)
+    })
+}
@@ -5,27 +5,39 @@ import typescript from '@rollup/plugin-typescript'
 
 const pkg = JSON.parse(readFileSync(join(cwd(), 'package.json'), 'utf8'))
 
+const dir = pkg.module.split('/')[1]
+
 export default {
-  input: 'guest-js/index.ts',
+  input: [
+    'guest-js/index.ts',
+    'guest-js/react.ts',
+    'guest-js/vue.ts',
+    'guest-js/svelte.ts'
+  ],
   output: [
     {
-      file: pkg.exports.import,
+      dir,
+      entryFileNames: '[name].js',
       format: 'esm'
     },
     {
-      file: pkg.exports.require,
+      dir,
+      entryFileNames: '[name].cjs',
       format: 'cjs'
     }
   ],
   plugins: [
     typescript({
       declaration: true,
-      declarationDir: `./${pkg.exports.import.split('/')[0]}`
+      declarationDir: `./${dir}`
     })
   ],
   external: [
     /^@tauri-apps\/api/,
     ...Object.keys(pkg.dependencies || {}),
-    ...Object.keys(pkg.peerDependencies || {})
+    // Subpaths like `svelte/store` are external too.
+    ...Object.keys(pkg.peerDependencies || {}).map(
+      (name) => new RegExp(`^${name}(/|$)`)
+    )
   ]
 }
@@ -0,0 +1,36 @@
+//! Print the permission statuses of an installed app as JSON.
+//!
+//! ```sh
+//! macos-permissions com.example.app
+//! ```
+
+use std::process::ExitCode;
+
+use serde_json::json;
+use tauri_plugin_macos_permissions::bundle_statuses;
+
+fn main() -> ExitCode {
+    let Some(bundle_id) = std::env::args().nth(1) else {
+        eprintln!("Usage: macos-permissions <bundle-id>");
+
+        return ExitCode::from(2);
+    };
+
+    let output = json!({
+        "bundleId": bundle_id,
+        "permissions": bundle_statuses(&bundle_id),
+    });
+
+    match serde_json::to_string_pretty(&output) {
+        Ok(output) => {
+            println!("{output}");
+
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,28 +1,34 @@
-use tauri::{command, AppHandle, Runtime};
+use std::{path::PathBuf, time::Duration};
 
-#[cfg(target_os = "macos")]
-use {
-    macos_accessibility_client::accessibility::{
-        application_is_trusted, application_is_trusted_with_prompt,
+use tauri::{command, ipc::Channel, AppHandle, Manager, Runtime};
+
+use crate::{
+    error::Error,
+    file_drop,
+    location::Position,
+    manager::PermissionsManager,
+    models::{
+        AccessibilityRequestOptions, AppCopies, AppIdentity, AppLocation, CaptureDevice,
+        EnsurePermissionOptions, EnsuredPermission, EventTapReadiness, MediaType, OsUpgraded,
+        PathAccessEntry, PermissionKind, PermissionState, PermissionStats, PermissionStatus,
+        PermissionUpdate, PluginInfo, Profile, ProfileReadiness, PromptHistory, RemoteSession,
+        RequestOutcome, ResponsibleProcess, ShortcutReadiness, StatusSummary, Verification,
     },
-    objc2::{class, msg_send, runtime::Bool},
-    objc2_foundation::NSString,
-    std::{fs::read_dir, process::Command},
-    tauri::Manager,
+    onboarding,
+    reminders::Reminders,
+    status::{device_connected, managed, shortcut_readiness},
+    upgrade::OsUpgrade,
 };
 
 #[cfg(target_os = "macos")]
-#[link(name = "CoreGraphics", kind = "framework")]
-extern "C" {
-    fn CGPreflightScreenCaptureAccess() -> bool;
-    fn CGRequestScreenCaptureAccess() -> bool;
-}
-
-#[cfg(target_os = "macos")]
-#[link(name = "IOKit", kind = "framework")]
-extern "C" {
-    fn IOHIDCheckAccess(request: u32) -> u32;
-}
+use {
+    crate::{
+        config::RequestStrategy, location, macos::*, models::AppCopy, notifications,
+        pppc::PppcProfile, prompt::prompting, tcc, verify::*,
+    },
+    macos_accessibility_client::accessibility::application_is_trusted,
+    std::path::Path,
+};
 
 /// Check accessibility permission.
 ///
@@ -37,7 +43,13 @@ extern "C" {
 /// println!("Authorized: {}", authorized); // false
 /// ```
 #[command]
+#[cfg_attr(feature = "specta", specta::specta)]
 pub async fn check_accessibility_permission() -> bool {
+    check_accessibility_permission_blocking()
+}
+
+/// Blocking variant of [`check_accessibility_permission`].
+pub fn check_accessibility_permission_blocking() -> bool {
     #[cfg(target_os = "macos")]
     return application_is_trusted();
 
@@ -47,16 +59,100 @@ pub async fn check_accessibility_permission() -> bool {
 
 /// Request accessibility permission.
 ///
+/// Fails with [`Error::TimedOut`] once the `requestTimeout` config elapses.
+///
+/// # Arguments
+/// - `options`: Whether to show the system prompt, open System Settings, or both.
+///   Defaults to only showing the prompt. System Settings is never opened with the
+///   `headlessRequests` config or the `prompt` request strategy.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted`, `Dismissed` once the prompt is closed, `OpenedSettings`,
+///   or `PromptUnavailable` if the user was already asked.
+///
 /// # Example
 /// ```
-/// use tauri_plugin_macos_permissions::request_accessibility_permission;
+/// use tauri_plugin_macos_permissions::{request_accessibility_permission, AccessibilityRequestOptions};
 ///
-/// request_accessibility_permission().await;
+/// let outcome = request_accessibility_permission(app_handle, Some(AccessibilityRequestOptions {
+///     prompt: true,
+///     open_settings: true,
+/// }))
+/// .await?;
+/// println!("Outcome: {:?}", outcome); // OpenedSettings
 /// ```
 #[command]
-pub async fn request_accessibility_permission() {
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_accessibility_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+    options: Option<AccessibilityRequestOptions>,
+) -> Result<RequestOutcome, Error> {
     #[cfg(target_os = "macos")]
-    application_is_trusted_with_prompt();
+    return run_request(&app_handle, PermissionKind::Accessibility, async {
+        ensure_gui_session()?;
+
+        let options = options.unwrap_or_default();
+        let open_settings =
+            options.open_settings && may_open_settings(&app_handle, PermissionKind::Accessibility);
+
+        if is_process_trusted(false) {
+            return Ok(RequestOutcome::Granted);
+        }
+
+        let manager = app_handle.state::<PermissionsManager>();
+
+        let prompt_shown = options.prompt && {
+            is_process_trusted(true);
+
+            wait_for_tcc_prompt().await
+        };
+
+        if prompt_shown {
+            manager.prompt_shown();
+        }
+
+        if open_settings {
+            manager.open_settings(PermissionKind::Accessibility)?;
+
+            manager.await_grant(PermissionKind::Accessibility);
+
+            return Ok(RequestOutcome::OpenedSettings);
+        }
+
+        if !prompt_shown {
+            return Ok(RequestOutcome::PromptUnavailable);
+        }
+
+        Ok(
+            if wait_for_tcc_response(|| is_process_trusted(false)).await {
+                RequestOutcome::Granted
+            } else {
+                RequestOutcome::Dismissed
+            },
+        )
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app_handle, options);
+
+        Ok(RequestOutcome::Unsupported)
+    }
+}
+
+/// Open the Accessibility pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_accessibility_settings;
+///
+/// open_accessibility_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_accessibility_settings() -> Result<(), Error> {
+    PermissionKind::Accessibility.open_settings()
 }
 
 /// Check full disk access permission.
@@ -72,21 +168,19 @@ pub async fn request_accessibility_permission() {
 /// println!("Authorized: {}", authorized); // false
 /// ```
 #[command]
+#[cfg_attr(feature = "specta", specta::specta)]
 pub async fn check_full_disk_access_permission<R: Runtime>(app_handle: AppHandle<R>) -> bool {
+    check_full_disk_access_permission_blocking(&app_handle)
+}
+
+/// Blocking variant of [`check_full_disk_access_permission`].
+pub fn check_full_disk_access_permission_blocking<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
     #[cfg(target_os = "macos")]
     {
-        // Reference: https://github.com/inket/FullDiskAccess/blob/846e04ea2b84fce843f47d7e7f3421189221829c/Sources/FullDiskAccess/FullDiskAccess.swift#L46
-        let check_dirs = vec!["Library/Containers/com.apple.stocks", "Library/Safari"];
-
-        if let Ok(home_dir) = app_handle.path().home_dir() {
-            for check_dir in check_dirs.iter() {
-                if read_dir(&home_dir.join(check_dir)).is_ok() {
-                    return true;
-                }
-            }
-        }
-
-        false
+        app_handle
+            .path()
+            .home_dir()
+            .is_ok_and(|home_dir| full_disk_access_probe(&home_dir).is_some())
     }
 
     #[cfg(not(target_os = "macos"))]
@@ -99,23 +193,68 @@ pub async fn check_full_disk_access_permission<R: Runtime>(app_handle: AppHandle
 
 /// Request full disk access permission.
 ///
+/// There is no system prompt for full disk access, this opens System Settings like `open_full_disk_access_settings`,
+/// unless the `headlessRequests` config is set or the request strategy is `prompt` or `silent`.
+/// Fails with [`Error::TimedOut`] once the `requestTimeout` config elapses.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted`, `OpenedSettings` if the permission is missing,
+///   `Dismissed` if the user declined the priming dialog, or `PromptUnavailable`
+///   with the `headlessRequests` config.
+///
 /// # Example
 /// ```
 /// use tauri_plugin_macos_permissions::request_full_disk_access_permission;
 ///
-/// request_full_disk_access_permission().await;
+/// let outcome = request_full_disk_access_permission(app_handle).await?;
+/// println!("Outcome: {:?}", outcome); // OpenedSettings
 /// ```
 #[command]
-pub async fn request_full_disk_access_permission() -> Result<(), String> {
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_full_disk_access_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<RequestOutcome, Error> {
     #[cfg(target_os = "macos")]
+    return run_request(&app_handle, PermissionKind::FullDiskAccess, async {
+        if check_full_disk_access_permission_blocking(&app_handle) {
+            return Ok(RequestOutcome::Granted);
+        }
+
+        // There is no prompt, every strategy but `prompt` opens System Settings.
+        if !may_open_settings(&app_handle, PermissionKind::FullDiskAccess) {
+            return Ok(RequestOutcome::PromptUnavailable);
+        }
+
+        let manager = app_handle.state::<PermissionsManager>();
+
+        manager.open_settings(PermissionKind::FullDiskAccess)?;
+
+        manager.await_grant(PermissionKind::FullDiskAccess);
+
+        Ok(RequestOutcome::OpenedSettings)
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
     {
-        Command::new("open")
-            .arg("x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles")
-            .output()
-            .map_err(|error| error.to_string())?;
+        let _ = app_handle;
+
+        Ok(RequestOutcome::Unsupported)
     }
+}
 
-    Ok(())
+/// Open the Full Disk Access pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_full_disk_access_settings;
+///
+/// open_full_disk_access_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_full_disk_access_settings() -> Result<(), Error> {
+    PermissionKind::FullDiskAccess.open_settings()
 }
 
 /// Check screen recording permission.
@@ -131,7 +270,13 @@ pub async fn request_full_disk_access_permission() -> Result<(), String> {
 /// println!("Authorized: {}", authorized); // false
 /// ```
 #[command]
+#[cfg_attr(feature = "specta", specta::specta)]
 pub async fn check_screen_recording_permission() -> bool {
+    check_screen_recording_permission_blocking()
+}
+
+/// Blocking variant of [`check_screen_recording_permission`].
+pub fn check_screen_recording_permission_blocking() -> bool {
     #[cfg(target_os = "macos")]
     unsafe {
         CGPreflightScreenCaptureAccess()
@@ -143,20 +288,74 @@ pub async fn check_screen_recording_permission() -> bool {
 
 /// Request screen recording permission.
 ///
+/// Resolves once the user responded to the system prompt. The prompt only appears the first
+/// time, afterwards the permission can only be granted in System Settings. Fails with
+/// [`Error::TimedOut`] once the `requestTimeout` config elapses.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted`, `Dismissed` once the prompt is closed, or `PromptUnavailable`
+///   if the user was already asked.
+///
 /// # Example
 /// ```
 /// use tauri_plugin_macos_permissions::request_screen_recording_permission;
 ///
-/// request_screen_recording_permission().await;
+/// let outcome = request_screen_recording_permission(app_handle).await?;
+/// println!("Outcome: {:?}", outcome); // Dismissed
 /// ```
 #[command]
-pub async fn request_screen_recording_permission() {
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_screen_recording_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<RequestOutcome, Error> {
     #[cfg(target_os = "macos")]
-    unsafe {
-        CGRequestScreenCaptureAccess();
+    return run_request(&app_handle, PermissionKind::ScreenRecording, async {
+        ensure_gui_session()?;
+
+        if unsafe { CGPreflightScreenCaptureAccess() } {
+            return Ok(RequestOutcome::Granted);
+        }
+
+        prompting(|| unsafe { CGRequestScreenCaptureAccess() });
+
+        if !wait_for_tcc_prompt().await {
+            return Ok(RequestOutcome::PromptUnavailable);
+        }
+
+        app_handle.state::<PermissionsManager>().prompt_shown();
+
+        Ok(
+            if wait_for_tcc_response(|| unsafe { CGPreflightScreenCaptureAccess() }).await {
+                RequestOutcome::Granted
+            } else {
+                RequestOutcome::Dismissed
+            },
+        )
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+
+        Ok(RequestOutcome::Unsupported)
     }
 }
 
+/// Open the Screen Recording pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_screen_recording_settings;
+///
+/// open_screen_recording_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_screen_recording_settings() -> Result<(), Error> {
+    PermissionKind::ScreenRecording.open_settings()
+}
+
 /// Check microphone permission.
 ///
 /// # Returns
@@ -170,17 +369,15 @@ pub async fn request_screen_recording_permission() {
 /// println!("Authorized: {}", authorized); // false
 /// ```
 #[command]
+#[cfg_attr(feature = "specta", specta::specta)]
 pub async fn check_microphone_permission() -> bool {
-    #[cfg(target_os = "macos")]
-    unsafe {
-        let av_media_type = NSString::from_str("soun");
-        let status: i32 = msg_send![
-            class!(AVCaptureDevice),
-            authorizationStatusForMediaType: &*av_media_type
-        ];
+    check_microphone_permission_blocking()
+}
 
-        status == 3
-    }
+/// Blocking variant of [`check_microphone_permission`].
+pub fn check_microphone_permission_blocking() -> bool {
+    #[cfg(target_os = "macos")]
+    return authorization_status(AV_MEDIA_TYPE_AUDIO) == AV_AUTHORIZATION_STATUS_AUTHORIZED;
 
     #[cfg(not(target_os = "macos"))]
     return true;
@@ -188,27 +385,57 @@ pub async fn check_microphone_permission() -> bool {
 
 /// Request microphone permission.
 ///
+/// Resolves once the user responded to the system prompt. Fails with [`Error::TimedOut`] once
+/// the `requestTimeout` config elapses.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted`, `Denied`, or `PromptUnavailable` if the permission is restricted.
+///
 /// # Example
 /// ```
 /// use tauri_plugin_macos_permissions::request_microphone_permission;
 ///
-/// request_microphone_permission().await;
+/// let outcome = request_microphone_permission(app_handle).await?;
+/// println!("Outcome: {:?}", outcome); // Granted
 /// ```
 #[command]
-pub async fn request_microphone_permission() -> Result<(), String> {
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_microphone_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<RequestOutcome, Error> {
     #[cfg(target_os = "macos")]
-    unsafe {
-        let av_media_type = NSString::from_str("soun");
-        type CompletionBlock = Option<extern "C" fn(Bool)>;
-        let completion_block: CompletionBlock = None;
-        let _: () = msg_send![
-            class!(AVCaptureDevice),
-            requestAccessForMediaType: &*av_media_type,
-            completionHandler: completion_block
-        ];
+    return run_request(&app_handle, PermissionKind::Microphone, async {
+        ensure_gui_session()?;
+
+        // The prompt is only shown while the user has not been asked.
+        if authorization_status(AV_MEDIA_TYPE_AUDIO) == 0 {
+            app_handle.state::<PermissionsManager>().prompt_shown();
+        }
+
+        Ok(request_media_access(AV_MEDIA_TYPE_AUDIO).await?)
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+
+        Ok(RequestOutcome::Unsupported)
     }
+}
 
-    Ok(())
+/// Open the Microphone pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_microphone_settings;
+///
+/// open_microphone_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_microphone_settings() -> Result<(), Error> {
+    PermissionKind::Microphone.open_settings()
 }
 
 /// Check camera permission.
@@ -224,17 +451,15 @@ pub async fn request_microphone_permission() -> Result<(), String> {
 /// println!("Authorized: {}", authorized); // false
 /// ```
 #[command]
+#[cfg_attr(feature = "specta", specta::specta)]
 pub async fn check_camera_permission() -> bool {
-    #[cfg(target_os = "macos")]
-    unsafe {
-        let av_media_type = NSString::from_str("vide");
-        let status: i32 = msg_send![
-            class!(AVCaptureDevice),
-            authorizationStatusForMediaType: &*av_media_type
-        ];
+    check_camera_permission_blocking()
+}
 
-        status == 3
-    }
+/// Blocking variant of [`check_camera_permission`].
+pub fn check_camera_permission_blocking() -> bool {
+    #[cfg(target_os = "macos")]
+    return authorization_status(AV_MEDIA_TYPE_VIDEO) == AV_AUTHORIZATION_STATUS_AUTHORIZED;
 
     #[cfg(not(target_os = "macos"))]
     return true;
@@ -242,27 +467,57 @@ pub async fn check_camera_permission() -> bool {
 
 /// Request camera permission.
 ///
+/// Resolves once the user responded to the system prompt. Fails with [`Error::TimedOut`] once
+/// the `requestTimeout` config elapses.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted`, `Denied`, or `PromptUnavailable` if the permission is restricted.
+///
 /// # Example
 /// ```
 /// use tauri_plugin_macos_permissions::request_camera_permission;
 ///
-/// request_camera_permission().await;
+/// let outcome = request_camera_permission(app_handle).await?;
+/// println!("Outcome: {:?}", outcome); // Granted
 /// ```
 #[command]
-pub async fn request_camera_permission() -> Result<(), String> {
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_camera_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<RequestOutcome, Error> {
     #[cfg(target_os = "macos")]
-    unsafe {
-        let av_media_type = NSString::from_str("vide");
-        type CompletionBlock = Option<extern "C" fn(Bool)>;
-        let completion_block: CompletionBlock = None;
-        let _: () = msg_send![
-            class!(AVCaptureDevice),
-            requestAccessForMediaType: &*av_media_type,
-            completionHandler: completion_block
-        ];
+    return run_request(&app_handle, PermissionKind::Camera, async {
+        ensure_gui_session()?;
+
+        // The prompt is only shown while the user has not been asked.
+        if authorization_status(AV_MEDIA_TYPE_VIDEO) == 0 {
+            app_handle.state::<PermissionsManager>().prompt_shown();
+        }
+
+        Ok(request_media_access(AV_MEDIA_TYPE_VIDEO).await?)
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+
+        Ok(RequestOutcome::Unsupported)
     }
+}
 
-    Ok(())
+/// Open the Camera pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_camera_settings;
+///
+/// open_camera_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_camera_settings() -> Result<(), Error> {
+    PermissionKind::Camera.open_settings()
 }
 
 /// Check input monitoring permission.
@@ -278,10 +533,16 @@ pub async fn request_camera_permission() -> Result<(), String> {
 /// println!("Authorized: {}", authorized); // false
 /// ```
 #[command]
+#[cfg_attr(feature = "specta", specta::specta)]
 pub async fn check_input_monitoring_permission() -> bool {
+    check_input_monitoring_permission_blocking()
+}
+
+/// Blocking variant of [`check_input_monitoring_permission`].
+pub fn check_input_monitoring_permission_blocking() -> bool {
     #[cfg(target_os = "macos")]
     unsafe {
-        let status = IOHIDCheckAccess(1);
+        let status = IOHIDCheckAccess(IOHID_REQUEST_TYPE_LISTEN_EVENT);
 
         status == 0
     }
@@ -292,21 +553,1775 @@ pub async fn check_input_monitoring_permission() -> bool {
 
 /// Request input monitoring permission.
 ///
+/// Shows the system prompt, it only appears once per app. Fails with [`Error::TimedOut`] once
+/// the `requestTimeout` config elapses.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted`, `Denied`, `Dismissed` once the prompt is closed, or
+///   `PromptUnavailable` if the user was already asked.
+///
 /// # Example
 /// ```
 /// use tauri_plugin_macos_permissions::request_input_monitoring_permission;
 ///
-/// request_input_monitoring_permission().await;
+/// let outcome = request_input_monitoring_permission(app_handle).await?;
+/// println!("Outcome: {:?}", outcome); // Denied
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_input_monitoring_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<RequestOutcome, Error> {
+    #[cfg(target_os = "macos")]
+    return run_request(&app_handle, PermissionKind::InputMonitoring, async {
+        ensure_gui_session()?;
+
+        // Reference: https://developer.apple.com/documentation/iokit/iohidaccesstype
+        let outcome = || match unsafe { IOHIDCheckAccess(IOHID_REQUEST_TYPE_LISTEN_EVENT) } {
+            0 => Some(RequestOutcome::Granted),
+            1 => Some(RequestOutcome::Denied),
+            _ => None,
+        };
+
+        if let Some(outcome) = outcome() {
+            return Ok(outcome);
+        }
+
+        if prompting(|| unsafe { IOHIDRequestAccess(IOHID_REQUEST_TYPE_LISTEN_EVENT) }) {
+            return Ok(RequestOutcome::Granted);
+        }
+
+        if !wait_for_tcc_prompt().await {
+            return Ok(RequestOutcome::PromptUnavailable);
+        }
+
+        app_handle.state::<PermissionsManager>().prompt_shown();
+
+        wait_for_tcc_response(|| outcome().is_some()).await;
+
+        Ok(outcome().unwrap_or(RequestOutcome::Dismissed))
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+
+        Ok(RequestOutcome::Unsupported)
+    }
+}
+
+/// Open the Input Monitoring pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_input_monitoring_settings;
+///
+/// open_input_monitoring_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_input_monitoring_settings() -> Result<(), Error> {
+    PermissionKind::InputMonitoring.open_settings()
+}
+
+/// Check location permission.
+///
+/// # Returns
+/// - `bool`: `true` if location permission is granted, `false` otherwise.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::check_location_permission;
+///
+/// let authorized = check_location_permission().await;
+/// println!("Authorized: {}", authorized); // false
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_location_permission() -> bool {
+    check_location_permission_blocking()
+}
+
+/// Blocking variant of [`check_location_permission`].
+pub fn check_location_permission_blocking() -> bool {
+    #[cfg(target_os = "macos")]
+    return location::location_services_enabled() && location::authorization_status() >= 3;
+
+    #[cfg(not(target_os = "macos"))]
+    return true;
+}
+
+/// Request location permission.
+///
+/// Resolves once the user responded to the system prompt. Fails with [`Error::TimedOut`] once
+/// the `requestTimeout` config elapses, without it the wait ends after two minutes.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted`, `Denied`, `Dismissed` if the prompt was not answered in time, or
+///   `PromptUnavailable` if location services are disabled or restricted.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::request_location_permission;
+///
+/// let outcome = request_location_permission(app_handle).await?;
+/// println!("Outcome: {:?}", outcome); // Granted
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_location_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<RequestOutcome, Error> {
+    #[cfg(target_os = "macos")]
+    return run_request(&app_handle, PermissionKind::Location, async {
+        ensure_gui_session()?;
+
+        if !location::location_services_enabled() {
+            return Ok(RequestOutcome::PromptUnavailable);
+        }
+
+        // Reference: https://developer.apple.com/documentation/corelocation/clauthorizationstatus
+        let outcome = || match location::authorization_status() {
+            0 => None,
+            1 => Some(RequestOutcome::PromptUnavailable),
+            2 => Some(RequestOutcome::Denied),
+            _ => Some(RequestOutcome::Granted),
+        };
+
+        if let Some(outcome) = outcome() {
+            return Ok(outcome);
+        }
+
+        location::request_authorization(&app_handle)?;
+
+        let manager = app_handle.state::<PermissionsManager>();
+
+        manager.prompt_shown();
+
+        // Woken by the location delegate once the user responds, polling is only a fallback.
+        let response = async {
+            loop {
+                manager
+                    .tick(&[PermissionKind::Location], Duration::from_secs(1))
+                    .await;
+
+                if let Some(outcome) = outcome() {
+                    return outcome;
+                }
+            }
+        };
+
+        // The prompt may be left open, give up after two minutes unless `requestTimeout` bounds the wait.
+        match manager.config().request_timeout(PermissionKind::Location) {
+            Some(_) => Ok(response.await),
+            None => Ok(tokio::time::timeout(Duration::from_secs(120), response)
+                .await
+                .unwrap_or(RequestOutcome::Dismissed)),
+        }
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+
+        Ok(RequestOutcome::Unsupported)
+    }
+}
+
+/// Open the Location Services pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_location_settings;
+///
+/// open_location_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_location_settings() -> Result<(), Error> {
+    PermissionKind::Location.open_settings()
+}
+
+/// Get the current position from CoreLocation, requires location permission.
+///
+/// # Arguments
+/// - `timeout`: How long to wait for a position in milliseconds, defaults to `10000`.
+///
+/// # Returns
+/// - `Position`: The current position.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::get_current_position;
+///
+/// let position = get_current_position(app_handle, None).await?;
+/// println!("Latitude: {}", position.latitude); // 31.2304
 /// ```
 #[command]
-pub async fn request_input_monitoring_permission() -> Result<(), String> {
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_current_position<R: Runtime>(
+    app_handle: AppHandle<R>,
+    timeout: Option<u64>,
+) -> Result<Position, Error> {
     #[cfg(target_os = "macos")]
     {
-        Command::new("open")
-            .arg("x-apple.systempreferences:com.apple.preference.security?Privacy_ListenEvent")
-            .output()
-            .map_err(|error| error.to_string())?;
+        if !check_location_permission().await {
+            return Err(Error::PermissionDenied(PermissionKind::Location));
+        }
+
+        let timeout = Duration::from_millis(timeout.unwrap_or(10000));
+
+        location::current_position(&app_handle, timeout).await
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app_handle, timeout);
+
+        Err(Error::Unsupported("Location is only supported on macOS"))
     }
+}
+
+/// Check notification permission.
+///
+/// # Returns
+/// - `bool`: `true` if notification permission is granted, `false` otherwise.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::check_notification_permission;
+///
+/// let authorized = check_notification_permission().await;
+/// println!("Authorized: {}", authorized); // false
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_notification_permission() -> bool {
+    check_notification_permission_blocking()
+}
+
+/// Blocking variant of [`check_notification_permission`].
+pub fn check_notification_permission_blocking() -> bool {
+    #[cfg(target_os = "macos")]
+    return notifications::authorization_status().is_some_and(|status| status >= 2);
+
+    #[cfg(not(target_os = "macos"))]
+    return true;
+}
+
+/// Request notification permission.
+///
+/// Fails with [`Error::TimedOut`] once the `requestTimeout` config elapses.
+///
+/// # Returns
+/// - `RequestOutcome`: `Granted` if the user allowed notifications, `Denied` otherwise.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::request_notification_permission;
+///
+/// let outcome = request_notification_permission(app_handle).await?;
+/// println!("Outcome: {:?}", outcome); // Granted
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_notification_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<RequestOutcome, Error> {
+    #[cfg(target_os = "macos")]
+    return run_request(&app_handle, PermissionKind::Notifications, async {
+        ensure_gui_session()?;
+
+        // The prompt is only shown while the user has not been asked.
+        if notifications::authorization_status() == Some(0) {
+            app_handle.state::<PermissionsManager>().prompt_shown();
+        }
+
+        let authorized = tauri::async_runtime::spawn_blocking(notifications::request_authorization)
+            .await
+            .map_err(|error| error.to_string())??;
+
+        Ok(if authorized {
+            RequestOutcome::Granted
+        } else {
+            RequestOutcome::Denied
+        })
+    })
+    .await;
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+
+        Ok(RequestOutcome::Unsupported)
+    }
+}
+
+/// Open the Notifications pane of System Settings, without showing a prompt.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_notification_settings;
+///
+/// open_notification_settings().await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_notification_settings() -> Result<(), Error> {
+    PermissionKind::Notifications.open_settings()
+}
+
+/// List the connected capture devices, requires the matching microphone or camera permission.
+///
+/// # Arguments
+/// - `media_type`: `Audio` for microphones, `Video` for cameras.
+///
+/// # Returns
+/// - `Vec<CaptureDevice>`: The connected capture devices.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{list_capture_devices, MediaType};
+///
+/// let devices = list_capture_devices(app_handle, MediaType::Audio).await?;
+/// println!("Devices: {:?}", devices); // [CaptureDevice { id: "BuiltInMicrophoneDevice", .. }]
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn list_capture_devices<R: Runtime>(
+    app_handle: AppHandle<R>,
+    media_type: MediaType,
+) -> Result<Vec<CaptureDevice>, Error> {
+    let permission = media_type.permission();
+
+    if !permission.check(&app_handle).await {
+        return Err(Error::PermissionDenied(permission));
+    }
+
+    #[cfg(target_os = "macos")]
+    return capture_devices(match media_type {
+        MediaType::Audio => AV_MEDIA_TYPE_AUDIO,
+        MediaType::Video => AV_MEDIA_TYPE_VIDEO,
+    })
+    .map_err(Error::from);
+
+    #[cfg(not(target_os = "macos"))]
+    return Ok(Vec::new());
+}
+
+/// Verify that microphone permission works by capturing audio for one second.
+///
+/// Catches grants that exist in System Settings while capture still fails or is silent, e.g. after an OS update.
+///
+/// # Returns
+/// - `Verification`: `Granted` if audio was captured, `Ineffective` with a reason if not, `Denied` without permission.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::verify_microphone_access;
+///
+/// let verification = verify_microphone_access().await?;
+/// println!("Status: {:?}", verification.status); // Granted
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn verify_microphone_access() -> Result<Verification, Error> {
+    if !check_microphone_permission().await {
+        return Ok(Verification::denied());
+    }
+
+    #[cfg(target_os = "macos")]
+    return tauri::async_runtime::spawn_blocking(|| verify_microphone(Duration::from_secs(1)))
+        .await
+        .map_err(|error| Error::Request(error.to_string()));
+
+    #[cfg(not(target_os = "macos"))]
+    return Ok(Verification::granted());
+}
+
+/// Verify that screen recording permission works by capturing a frame and reading window titles.
+///
+/// The permission is reported as granted right away, while capture keeps returning black frames until the app is relaunched.
+///
+/// # Returns
+/// - `Verification`: `Granted` if the screen content is visible, `Ineffective` with a reason if not, `Denied` without permission.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::verify_screen_recording_access;
+///
+/// let verification = verify_screen_recording_access().await?;
+/// println!("Status: {:?}", verification.status); // Ineffective
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn verify_screen_recording_access() -> Result<Verification, Error> {
+    if !check_screen_recording_permission().await {
+        return Ok(Verification::denied());
+    }
+
+    #[cfg(target_os = "macos")]
+    return tauri::async_runtime::spawn_blocking(verify_screen_recording)
+        .await
+        .map_err(|error| Error::Request(error.to_string()));
+
+    #[cfg(not(target_os = "macos"))]
+    return Ok(Verification::granted());
+}
+
+/// Verify that accessibility permission works by reading the title of the focused app.
+///
+/// # Returns
+/// - `Verification`: `Granted` if the query succeeded, `Ineffective` with a reason if the trust is listed but rejected,
+///   `Denied` without permission.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::verify_accessibility_access;
+///
+/// let verification = verify_accessibility_access().await?;
+/// println!("Status: {:?}", verification.status); // Granted
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn verify_accessibility_access() -> Result<Verification, Error> {
+    if !check_accessibility_permission().await {
+        return Ok(Verification::denied());
+    }
+
+    #[cfg(target_os = "macos")]
+    return tauri::async_runtime::spawn_blocking(verify_accessibility)
+        .await
+        .map_err(|error| Error::Request(error.to_string()));
+
+    #[cfg(not(target_os = "macos"))]
+    return Ok(Verification::granted());
+}
+
+/// Check everything a global hotkey or key listener needs to install a keyboard event tap.
+///
+/// Combines accessibility trust, input monitoring and the creation of a listen-only test tap,
+/// which is only attempted once both are granted.
+///
+/// # Returns
+/// - `EventTapReadiness`: Whether the tap can be installed, and which permissions are missing.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::check_event_tap_readiness;
+///
+/// let readiness = check_event_tap_readiness().await;
+/// println!("Missing: {:?}", readiness.missing); // [InputMonitoring]
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_event_tap_readiness() -> EventTapReadiness {
+    let accessibility = check_accessibility_permission().await;
+    let input_monitoring = check_input_monitoring_permission().await;
+
+    // The test tap would prompt for input monitoring, checks never do.
+    #[cfg(target_os = "macos")]
+    let event_tap = accessibility && input_monitoring && can_create_event_tap();
+
+    #[cfg(not(target_os = "macos"))]
+    let event_tap = true;
+
+    let missing = [
+        (PermissionKind::Accessibility, accessibility),
+        (PermissionKind::InputMonitoring, input_monitoring),
+    ]
+    .into_iter()
+    .filter_map(|(kind, granted)| (!granted).then_some(kind))
+    .collect();
+
+    EventTapReadiness {
+        ready: accessibility && input_monitoring && event_tap,
+        accessibility,
+        input_monitoring,
+        event_tap,
+        missing,
+    }
+}
+
+/// Check all permissions of a predefined profile.
+///
+/// # Arguments
+/// - `profile`: The profile to check, e.g. `Profile::ScreenRecorder`.
+///
+/// # Returns
+/// - `ProfileReadiness`: Whether the profile is ready, with the granted and missing permissions.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{check_profile, Profile};
+///
+/// let readiness = check_profile(app_handle, Profile::MeetingApp).await;
+/// println!("Missing: {:?}", readiness.missing); // [Camera, ScreenRecording]
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_profile<R: Runtime>(
+    app_handle: AppHandle<R>,
+    profile: Profile,
+) -> ProfileReadiness {
+    let mut granted = Vec::new();
+    let mut missing = Vec::new();
+
+    for &kind in profile.permissions() {
+        if kind.check(&app_handle).await {
+            granted.push(kind);
+        } else {
+            missing.push(kind);
+        }
+    }
+
+    ProfileReadiness {
+        profile,
+        ready: missing.is_empty(),
+        granted,
+        missing,
+    }
+}
+
+/// Check whether the app is listed in the Privacy & Security pane of a permission, granted or denied.
+///
+/// Tells whether the user has already been asked. Accessibility, full disk access and screen recording
+/// only report this once granted, or when the TCC database is readable with full disk access.
+///
+/// # Arguments
+/// - `kind`: The permission to check.
+///
+/// # Returns
+/// - `Option<bool>`: `true` if the app is listed, `false` if not, `None` if it cannot be determined.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{has_been_requested, PermissionKind};
+///
+/// let requested = has_been_requested(app_handle, PermissionKind::Camera).await;
+/// println!("Requested: {:?}", requested); // Some(false)
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn has_been_requested<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kind: PermissionKind,
+) -> Option<bool> {
+    let status = app_handle
+        .state::<PermissionsManager>()
+        .refresh(&app_handle, kind);
+
+    match status {
+        PermissionStatus::Unsupported | PermissionStatus::Restricted => None,
+        PermissionStatus::NotDetermined => Some(false),
+        PermissionStatus::Granted => Some(true),
+        PermissionStatus::Denied => match kind {
+            // Not granted yet is reported as denied, whether the user was asked or not.
+            PermissionKind::Accessibility
+            | PermissionKind::FullDiskAccess
+            | PermissionKind::ScreenRecording => {
+                #[cfg(target_os = "macos")]
+                return tcc::has_record(app_handle.path().home_dir().ok(), kind);
+
+                #[cfg(not(target_os = "macos"))]
+                return None;
+            }
+            _ => Some(true),
+        },
+    }
+}
+
+/// Get when the user last decided on a permission, read from the TCC databases.
+///
+/// Lets apps implement "we asked you 30 days ago" from the system's own records. Only available with
+/// full disk access, which the TCC databases require, and not in App Store builds.
+///
+/// # Arguments
+/// - `kind`: The permission to look up.
+///
+/// # Returns
+/// - `Option<PromptHistory>`: When the prompt was answered, the decision last changed and reminded of, `None`
+///   without a record.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{get_prompt_history, PermissionKind};
+///
+/// let history = get_prompt_history(app_handle, PermissionKind::ScreenRecording).await;
+/// println!("Last prompted: {:?}", history.and_then(|history| history.last_prompted)); // Some(1718000000000)
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_prompt_history<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kind: PermissionKind,
+) -> Option<PromptHistory> {
+    #[cfg(target_os = "macos")]
+    return tcc::prompt_history(app_handle.path().home_dir().ok().as_deref(), kind);
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app_handle, kind);
+
+        None
+    }
+}
+
+/// Generate a PPPC configuration profile pre-approving the given permissions, for IT admins deploying the app with MDM.
+///
+/// Uses the bundle identifier and the designated requirement of the running app, which has to be signed.
+/// Only accessibility and full disk access can be granted by a profile, see [`PppcProfile`](crate::PppcProfile).
+///
+/// # Arguments
+/// - `permissions`: The permissions the app needs.
+///
+/// # Returns
+/// - `String`: The `.mobileconfig` property list.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{generate_pppc_profile, PermissionKind};
+///
+/// let profile = generate_pppc_profile(app_handle, vec![PermissionKind::Accessibility]).await?;
+/// println!("{}", profile); // <?xml version="1.0" encoding="UTF-8"?>...
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn generate_pppc_profile<R: Runtime>(
+    app_handle: AppHandle<R>,
+    permissions: Vec<PermissionKind>,
+) -> Result<String, Error> {
+    #[cfg(target_os = "macos")]
+    {
+        let profile = PppcProfile {
+            identifier: app_handle.config().identifier.clone(),
+            code_requirement: designated_requirement()?,
+            permissions,
+            organization: None,
+        };
+
+        return Ok(profile.to_mobileconfig());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app_handle, permissions);
+
+        Err(Error::Unsupported(
+            "PPPC profiles can only be generated on macOS",
+        ))
+    }
+}
+
+/// Check whether the app runs translocated or quarantined.
+///
+/// Apps opened right from Downloads run from a randomized read-only path until the user moves them,
+/// accessibility and full disk access grants are then lost on the next launch.
+///
+/// # Returns
+/// - `AppLocation`: The bundle path, and whether it is translocated or quarantined.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::check_app_location;
+///
+/// let location = check_app_location().await;
+/// println!("Translocated: {}", location.translocated); // false
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_app_location() -> AppLocation {
+    #[cfg(target_os = "macos")]
+    {
+        let path = bundle_path();
+
+        AppLocation {
+            translocated: path.contains("/AppTranslocation/"),
+            quarantined: is_quarantined(&path),
+            path,
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    AppLocation {
+        path: std::env::current_exe()
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        translocated: false,
+        quarantined: false,
+    }
+}
+
+/// Get how the running app is identified, to debug grants remembered for another copy of it.
+///
+/// TCC keys grants by bundle identifier and code signature, a rebuilt, re-signed or bare dev binary
+/// is a different app to it.
+///
+/// # Returns
+/// - `AppIdentity`: The executable path, bundle identifier, signature, and whether the app is bundled.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::get_app_identity;
+///
+/// let identity = get_app_identity().await;
+/// println!("Bundle id: {:?}", identity.bundle_id); // Some("com.example.app")
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_app_identity() -> AppIdentity {
+    let executable_path = std::env::current_exe()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    #[cfg(target_os = "macos")]
+    {
+        let (signing_identity, team_id) = code_signature();
+
+        AppIdentity {
+            executable_path,
+            bundle_id: bundle_identifier(),
+            team_id,
+            signing_identity,
+            bundled: bundle_path().ends_with(".app"),
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    AppIdentity {
+        executable_path,
+        bundle_id: None,
+        team_id: None,
+        signing_identity: None,
+        bundled: false,
+    }
+}
+
+/// Get the process TCC attributes the permissions of the app to.
+///
+/// Launched from a terminal or an IDE, e.g. with `tauri dev`, the app is not responsible for
+/// itself: its prompts name the terminal, and the grants in System Settings are the terminal's.
+///
+/// # Returns
+/// - `Option<ResponsibleProcess>`: The responsible process, whether it is the app itself, and what to
+///   do otherwise. `None` if it cannot be determined, as in App Store builds.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::get_responsible_process;
+///
+/// let responsible = get_responsible_process().await;
+/// println!("Bundle id: {:?}", responsible.and_then(|process| process.bundle_id)); // Some("com.apple.Terminal")
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_responsible_process() -> Option<ResponsibleProcess> {
+    #[cfg(target_os = "macos")]
+    {
+        let pid = responsible_pid()?;
+        let path = process_path(pid)?;
+
+        // Helpers are nested in their app, e.g. `Code Helper.app` in `Visual Studio Code.app`.
+        let bundle_path = Path::new(&path)
+            .ancestors()
+            .filter(|ancestor| {
+                ancestor
+                    .extension()
+                    .is_some_and(|extension| extension == "app")
+            })
+            .last()
+            .map(|bundle_path| bundle_path.to_string_lossy().into_owned());
+
+        let is_self = pid == std::process::id() as i32;
+
+        let guidance = (!is_self).then(|| {
+            let name = bundle_path
+                .as_deref()
+                .unwrap_or(&path)
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches(".app");
+
+            format!(
+                "The app was launched by {name}, which is responsible for its permissions. The prompts \
+                 name {name} and the grants in System Settings are listed for it, launch the app bundle \
+                 from Finder or with `open` to test the app's own grants."
+            )
+        });
+
+        Some(ResponsibleProcess {
+            pid,
+            bundle_id: bundle_path.as_deref().and_then(bundle_identifier_of),
+            bundle_path,
+            path,
+            is_self,
+            guidance,
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    None
+}
+
+/// Find the other copies of the app, e.g. one in `/Applications` and one left in `~/Downloads`.
+///
+/// Users often grant the permissions to one copy and launch another, which TCC treats as a
+/// different app unless it is signed the same.
+///
+/// # Returns
+/// - `AppCopies`: Every copy with its version and whether it shares the grants of the running one,
+///   and the copy that likely holds the grants instead.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::find_app_copies;
+///
+/// let copies = find_app_copies().await;
+/// println!("Granted copy: {:?}", copies.likely_granted); // Some("/Applications/Example.app")
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn find_app_copies() -> AppCopies {
+    #[cfg(target_os = "macos")]
+    return tauri::async_runtime::spawn_blocking(app_copies_blocking)
+        .await
+        .unwrap_or(AppCopies {
+            copies: Vec::new(),
+            likely_granted: None,
+        });
+
+    #[cfg(not(target_os = "macos"))]
+    AppCopies {
+        copies: Vec::new(),
+        likely_granted: None,
+    }
+}
+
+/// Blocking part of [`find_app_copies`], `codesign` is spawned for every copy.
+#[cfg(target_os = "macos")]
+fn app_copies_blocking() -> AppCopies {
+    let current_path = bundle_path();
+    let mut paths = bundle_identifier()
+        .map(|bundle_id| app_copies(&bundle_id))
+        .unwrap_or_default();
+
+    // A translocated copy is not known to LaunchServices by its running path.
+    if !paths.contains(&current_path) {
+        paths.insert(0, current_path.clone());
+    }
+
+    // Signatures cannot be read from the sandbox.
+    let readable = !cfg!(feature = "app-store");
+    let signature = code_signature();
+
+    let copies: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let current = path == current_path;
+
+            // Ad-hoc signatures are pinned to the exact build, unsigned copies never share grants.
+            let shares_grants = readable.then(|| {
+                current
+                    || (signature.0.is_some()
+                        && signature.0.as_deref() != Some("adhoc")
+                        && code_signature_of(&path) == signature)
+            });
+
+            AppCopy {
+                version: bundle_version(&path),
+                path,
+                current,
+                shares_grants,
+            }
+        })
+        .collect();
+
+    let in_applications = |path: &str| path.contains("/Applications/");
+
+    let likely_granted = copies
+        .iter()
+        .find(|copy| {
+            !copy.current && copy.shares_grants == Some(false) && in_applications(&copy.path)
+        })
+        .filter(|_| !in_applications(&current_path))
+        .map(|copy| copy.path.clone());
+
+    AppCopies {
+        copies,
+        likely_granted,
+    }
+}
+
+/// Check whether the user is connected remotely, where permission prompts may not be presentable.
+///
+/// Prompts only appear on the Mac's own display, requests made over SSH, Screen Sharing or
+/// Remote Management resolve with `RemoteSession` instead of waiting for them.
+///
+/// # Returns
+/// - `Option<RemoteSession>`: How the user is connected with guidance to show them, `None` for a local session.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::check_remote_session;
+///
+/// let session = check_remote_session().await;
+/// println!("Session: {:?}", session.map(|session| session.kind)); // Some(ScreenSharing)
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_remote_session() -> Option<RemoteSession> {
+    #[cfg(target_os = "macos")]
+    return remote_session().map(RemoteSession::from);
+
+    #[cfg(not(target_os = "macos"))]
+    None
+}
+
+/// Check whether the app runs in a virtual machine.
+///
+/// Cameras, microphones and screen recording behave differently in VMs, as on VM-based CI runners.
+///
+/// # Returns
+/// - `bool`: `true` if the Mac is a virtual machine, `false` otherwise.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::is_virtual_machine;
+///
+/// let virtual_machine = is_virtual_machine().await;
+/// println!("Virtual machine: {}", virtual_machine); // false
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn is_virtual_machine() -> bool {
+    #[cfg(target_os = "macos")]
+    return crate::macos::is_virtual_machine();
+
+    #[cfg(not(target_os = "macos"))]
+    false
+}
+
+/// Check whether System Integrity Protection is enabled.
+///
+/// With SIP turned off, as on some test and enterprise machines, the TCC database can be edited
+/// by other processes and grants may change without going through System Settings.
+///
+/// # Returns
+/// - `Option<bool>`: `true` if no protection of SIP is turned off, `false` otherwise,
+///   `None` if it cannot be checked, as with the `app-store` feature or on other platforms.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::is_sip_enabled;
+///
+/// let enabled = is_sip_enabled().await;
+/// println!("SIP enabled: {:?}", enabled); // Some(true)
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn is_sip_enabled() -> Option<bool> {
+    #[cfg(target_os = "macos")]
+    return crate::macos::is_sip_enabled();
+
+    #[cfg(not(target_os = "macos"))]
+    None
+}
+
+/// Get the version and build configuration of the plugin, for bug reports and remote diagnostics.
+///
+/// # Returns
+/// - `PluginInfo`: The plugin version, the SDK and deployment target it was built with, its enabled
+///   features, the running macOS version and whether SIP is enabled.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::get_plugin_info;
+///
+/// let info = get_plugin_info().await;
+/// println!("Features: {:?}", info.features); // ["app-store"]
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_plugin_info() -> PluginInfo {
+    let features = [
+        ("app-store", cfg!(feature = "app-store")),
+        ("bin", cfg!(feature = "bin")),
+        ("macros", cfg!(feature = "macros")),
+        ("specta", cfg!(feature = "specta")),
+        ("swift", cfg!(feature = "swift")),
+    ]
+    .into_iter()
+    .filter(|&(_, enabled)| enabled)
+    .map(|(feature, _)| feature.to_string())
+    .collect();
+
+    #[cfg(target_os = "macos")]
+    let os_version = crate::macos::os_version();
+
+    #[cfg(not(target_os = "macos"))]
+    let os_version = None;
+
+    PluginInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        sdk_version: option_env!("MACOS_PERMISSIONS_SDK_VERSION").map(str::to_string),
+        deployment_target: option_env!("MACOS_PERMISSIONS_DEPLOYMENT_TARGET").map(str::to_string),
+        features,
+        os_version,
+        sip_enabled: is_sip_enabled().await,
+    }
+}
+
+/// Get the counters of the current session, e.g. to spot a frontend polling too often or to measure onboarding.
+///
+/// System Settings opened with the `open*Settings` commands is not counted, the app knows about those itself.
+///
+/// # Returns
+/// - `PermissionStats`: How many checks were performed, prompts shown, System Settings opened and grants
+///   observed, and how long the session lasts.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::get_permission_stats;
+///
+/// let stats = get_permission_stats(app_handle).await;
+/// println!("Prompts: {}", stats.prompts_shown); // 2
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_permission_stats<R: Runtime>(app_handle: AppHandle<R>) -> PermissionStats {
+    app_handle.state::<PermissionsManager>().stats()
+}
+
+/// Read `paths`, reporting which access is missing for the ones the app cannot read.
+///
+/// The `fileDrop` config runs the same on dropped files. Reading paths in the Desktop, Documents or Downloads
+/// folder, iCloud Drive or on another volume shows the Files & Folders prompt if the user was not asked yet,
+/// so call it when the app is about to use the paths.
+///
+/// # Arguments
+/// - `paths`: The files or folders to read.
+///
+/// # Returns
+/// - `Vec<PathAccessEntry>`: Each path with `Readable`, the missing access, or `Missing` if it does not exist.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::request_path_access;
+///
+/// let entries = request_path_access(app_handle, vec!["/Users/me/Library/Mail".into()]).await;
+/// println!("Access: {:?}", entries[0].access); // FullDiskAccess
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn request_path_access<R: Runtime>(
+    app_handle: AppHandle<R>,
+    paths: Vec<String>,
+) -> Vec<PathAccessEntry> {
+    let home_dir = app_handle.path().home_dir().ok();
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        file_drop::check_paths(home_dir.as_deref(), &paths)
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// Check whether global shortcuts can be registered.
+///
+/// Hotkeys registered by `tauri-plugin-global-shortcut` only need a GUI session. Shortcuts observed
+/// with an event tap, such as modifier-only ones, also need accessibility and input monitoring.
+///
+/// # Arguments
+/// - `event_tap`: Whether the shortcuts are observed with an event tap, defaults to `false`.
+///
+/// # Returns
+/// - `ShortcutReadiness`: Whether the shortcuts can be registered, and the first missing prerequisite.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::check_global_shortcut_readiness;
+///
+/// let readiness = check_global_shortcut_readiness(Some(true)).await;
+/// println!("Blocker: {:?}", readiness.blocker); // Some(InputMonitoringMissing)
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_global_shortcut_readiness(event_tap: Option<bool>) -> ShortcutReadiness {
+    shortcut_readiness(event_tap.unwrap_or_default())
+}
+
+/// Emit [`GLOBAL_SHORTCUT_READY_EVENT`](crate::GLOBAL_SHORTCUT_READY_EVENT) once global shortcuts can be registered.
+///
+/// # Arguments
+/// - `event_tap`: Whether the shortcuts are observed with an event tap, defaults to `false`.
+/// - `interval`: The polling interval in milliseconds, defaults to the `watchInterval` config or `1000`.
+///
+/// # Returns
+/// - `u32`: The watcher id, pass it to `unwatch` to stop waiting.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::watch_global_shortcut_readiness;
+///
+/// let id = watch_global_shortcut_readiness(app_handle, Some(true), None).await;
+/// println!("Watcher: {}", id); // 1
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn watch_global_shortcut_readiness<R: Runtime>(
+    app_handle: AppHandle<R>,
+    event_tap: Option<bool>,
+    interval: Option<u64>,
+) -> u32 {
+    let manager = app_handle.state::<PermissionsManager>();
+    let interval = Duration::from_millis(interval.unwrap_or(manager.config().watch_interval));
+
+    manager.watchers().watch_global_shortcut_readiness(
+        app_handle.clone(),
+        event_tap.unwrap_or_default(),
+        interval,
+    )
+}
+
+/// Get a compact overview of several permissions, e.g. to show in a tray menu.
+///
+/// # Arguments
+/// - `kinds`: The permissions to include, defaults to the `permissions` config.
+///
+/// # Returns
+/// - `StatusSummary`: The status of each permission, whether all are granted and the missing ones.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::get_status_summary;
+///
+/// let summary = get_status_summary(app_handle, None).await;
+/// println!("Missing: {:?}", summary.missing); // [ScreenRecording]
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_status_summary<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kinds: Option<Vec<PermissionKind>>,
+) -> StatusSummary {
+    let manager = app_handle.state::<PermissionsManager>();
+    let kinds = kinds.unwrap_or_else(|| manager.config().permissions.clone());
+
+    manager.summary(&app_handle, &kinds)
+}
+
+/// Get the macOS major upgrade detected on this launch, with the re-verified permissions.
+///
+/// Upgrades regularly reset or re-scope grants. The same payload is emitted as
+/// [`OS_UPGRADED_EVENT`](crate::OS_UPGRADED_EVENT), which may fire before the frontend listens.
+///
+/// # Returns
+/// - `Option<OsUpgraded>`: The previous and running version with a status summary, `None` without an upgrade.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::get_os_upgrade;
+///
+/// let upgraded = get_os_upgrade(app_handle).await;
+/// println!("Upgraded from: {:?}", upgraded.map(|upgraded| upgraded.previous_version)); // Some("14.6")
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn get_os_upgrade<R: Runtime>(app_handle: AppHandle<R>) -> Option<OsUpgraded> {
+    app_handle.state::<OsUpgrade>().get()
+}
+
+/// Open the built-in onboarding window, listing the permissions with their live status.
+///
+/// Emits [`ONBOARDING_FINISHED_EVENT`](crate::ONBOARDING_FINISHED_EVENT) once the window is closed.
+///
+/// # Arguments
+/// - `kinds`: The permissions to list, defaults to the `permissions` config.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{open_onboarding, PermissionKind};
+///
+/// open_onboarding(app_handle, Some(vec![PermissionKind::Accessibility])).await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_onboarding<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kinds: Option<Vec<PermissionKind>>,
+) -> Result<(), Error> {
+    let kinds = match kinds {
+        Some(kinds) => kinds,
+        None => app_handle
+            .state::<PermissionsManager>()
+            .config()
+            .permissions
+            .clone(),
+    };
+
+    onboarding::open(&app_handle, kinds).map_err(|error| Error::Request(error.to_string()))
+}
+
+/// Never remind the user about a permission again, e.g. when they pick "Don't ask again".
+///
+/// Persisted across launches, see the `reminders` config.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{dismiss_reminder, PermissionKind};
+///
+/// dismiss_reminder(app_handle, PermissionKind::ScreenRecording).await;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn dismiss_reminder<R: Runtime>(app_handle: AppHandle<R>, kind: PermissionKind) {
+    app_handle.state::<Reminders>().dismiss(kind);
+}
+
+/// Forget the reminders of a permission, or of every permission, so they start over.
+///
+/// # Arguments
+/// - `kind`: The permission to reset, defaults to all of them.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::reset_reminders;
+///
+/// reset_reminders(app_handle, None).await;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn reset_reminders<R: Runtime>(app_handle: AppHandle<R>, kind: Option<PermissionKind>) {
+    app_handle.state::<Reminders>().reset(kind);
+}
+
+/// Check a privacy service the plugin does not model, by its raw TCC identifier.
+///
+/// Uses the private TCC framework, the status is `unsupported` where it is not available.
+///
+/// # Arguments
+/// - `service`: The TCC service, e.g. `kTCCServiceCalendar`.
+///
+/// # Returns
+/// - `PermissionStatus`: The status of the service for the app.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::check_tcc_service;
+///
+/// let status = check_tcc_service("kTCCServiceCalendar".into()).await?;
+/// println!("Status: {:?}", status); // NotDetermined
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_tcc_service(service: String) -> Result<PermissionStatus, Error> {
+    if !service.starts_with("kTCCService") {
+        return Err(Error::InvalidService(service));
+    }
+
+    #[cfg(target_os = "macos")]
+    return Ok(tcc::service_status(&service));
+
+    #[cfg(not(target_os = "macos"))]
+    return Ok(PermissionStatus::Unsupported);
+}
+
+/// Open the Privacy & Security pane of a privacy service the plugin does not model, by its raw TCC identifier.
+///
+/// # Arguments
+/// - `service`: The TCC service, e.g. `kTCCServiceCalendar`.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::open_settings_for_service;
+///
+/// open_settings_for_service("kTCCServiceCalendar".into()).await?;
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_settings_for_service(service: String) -> Result<(), Error> {
+    #[cfg(target_os = "macos")]
+    {
+        ensure_gui_session()?;
+
+        let Some(anchor) = tcc::settings_anchor(&service) else {
+            return Err(Error::InvalidService(service));
+        };
+
+        return open_privacy_settings(&anchor).map_err(Error::from);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        if !service.starts_with("kTCCService") {
+            return Err(Error::InvalidService(service));
+        }
+
+        Ok(())
+    }
+}
+
+/// Check a permission, request it only if it is missing, and optionally wait until it is granted.
+///
+/// The request follows the `requestStrategies` config like the request commands, and fails with
+/// [`Error::TimedOut`] once the `requestTimeout` config elapses. Without it, waiting for the grant
+/// ends after five minutes, resolving with `granted: false`.
+///
+/// # Arguments
+/// - `kind`: The permission to ensure.
+/// - `options`: Whether to request the permission, and to wait for the grant after System Settings was opened.
+///   Defaults to requesting without waiting.
+/// - `cancel_id`: An id to stop early with `cancel`, failing with [`Error::Cancelled`].
+///
+/// # Returns
+/// - `EnsuredPermission`: Whether the permission is granted, its status, and the outcome of the request if one was made.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{ensure_permission, EnsurePermissionOptions, PermissionKind};
+///
+/// let ensured = ensure_permission(
+///     app_handle,
+///     PermissionKind::FullDiskAccess,
+///     Some(EnsurePermissionOptions { request: true, wait: true }),
+///     None,
+/// )
+/// .await?;
+/// println!("Granted: {}", ensured.granted); // true
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn ensure_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kind: PermissionKind,
+    options: Option<EnsurePermissionOptions>,
+    cancel_id: Option<u32>,
+) -> Result<EnsuredPermission, Error> {
+    let options = options.unwrap_or_default();
+    let manager = app_handle.state::<PermissionsManager>();
+    let status = manager.refresh(&app_handle, kind);
+
+    if status.is_granted() || !options.request {
+        return Ok(EnsuredPermission {
+            kind,
+            granted: status.is_granted(),
+            status,
+            outcome: None,
+        });
+    }
+
+    let ensure = async {
+        let outcome = kind.request(&app_handle).await?;
+
+        #[cfg(target_os = "macos")]
+        if options.wait && outcome == RequestOutcome::OpenedSettings {
+            let granted = async {
+                while !manager.refresh(&app_handle, kind).is_granted() {
+                    tokio::time::sleep(Duration::from_millis(250)).await;
+                }
+
+                Ok(())
+            };
+
+            // The user may never turn it on, give up after five minutes unless `requestTimeout` bounds the wait.
+            match manager.config().request_timeout(kind) {
+                Some(_) => with_timeout(&app_handle, kind, granted).await?,
+                None => {
+                    let _ = tokio::time::timeout(Duration::from_secs(300), granted).await;
+                }
+            }
+        }
+
+        let status = manager.refresh(&app_handle, kind);
+
+        Ok(EnsuredPermission {
+            kind,
+            granted: status.is_granted(),
+            status,
+            outcome: Some(outcome),
+        })
+    };
+
+    match cancel_id {
+        Some(id) => manager
+            .watchers()
+            .cancellable(id, ensure)
+            .await?
+            .unwrap_or(Err(Error::Cancelled(kind))),
+        None => ensure.await,
+    }
+}
+
+/// Open the System Settings pane of a permission and wait until the user is done there.
+///
+/// Resolves once System Settings is closed or the status of the permission changes, whichever comes first.
+///
+/// # Arguments
+/// - `kind`: The permission whose pane to open.
+/// - `cancel_id`: An id to stop waiting early with `cancel`, failing with [`Error::Cancelled`].
+///
+/// # Returns
+/// - `PermissionStatus`: The status of the permission when the user is done.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{open_settings_and_wait, PermissionKind};
+///
+/// let status = open_settings_and_wait(app_handle, PermissionKind::FullDiskAccess, None).await?;
+/// println!("Status: {:?}", status); // Granted
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn open_settings_and_wait<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kind: PermissionKind,
+    cancel_id: Option<u32>,
+) -> Result<PermissionStatus, Error> {
+    #[cfg(target_os = "macos")]
+    {
+        let manager = app_handle.state::<PermissionsManager>();
+        let wait = open_settings_and_wait_for(&app_handle, kind);
+
+        return match cancel_id {
+            Some(id) => manager
+                .watchers()
+                .cancellable(id, wait)
+                .await?
+                .unwrap_or(Err(Error::Cancelled(kind))),
+            None => wait.await,
+        };
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app_handle, kind, cancel_id);
+
+        Ok(PermissionStatus::Granted)
+    }
+}
+
+/// The wait of [`open_settings_and_wait`], without cancellation.
+#[cfg(target_os = "macos")]
+async fn open_settings_and_wait_for<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+) -> Result<PermissionStatus, Error> {
+    with_timeout(app_handle, kind, async {
+        let manager = app_handle.state::<PermissionsManager>();
+        let initial = manager.refresh(app_handle, kind);
+
+        // Observe before opening, System Settings may be closed right away.
+        let mut observer = observe_termination(SYSTEM_SETTINGS_BUNDLE_ID)?;
+
+        manager.open_settings(kind)?;
+
+        loop {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+
+            let closed = observer.terminated.try_recv().is_ok();
+            let status = manager.refresh(app_handle, kind);
+
+            if closed || status != initial {
+                return Ok(status);
+            }
+        }
+    })
+    .await
+}
+
+/// Watch a single permission, emitting an event whenever it changes.
+///
+/// # Arguments
+/// - `kind`: The permission to watch.
+/// - `interval`: The polling interval in milliseconds, defaults to the `watchInterval` config or `1000`.
+/// - `targets`: The labels of the windows or webviews receiving the events, defaults to the `eventTargets` config.
+///
+/// # Returns
+/// - `u32`: The watcher id, pass it to `unwatch` to stop watching.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{watch_permission, PermissionKind};
+///
+/// let id = watch_permission(app_handle, PermissionKind::Accessibility, None, None).await;
+/// println!("Watcher: {}", id); // 0
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn watch_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kind: PermissionKind,
+    interval: Option<u64>,
+    targets: Option<Vec<String>>,
+) -> u32 {
+    let manager = app_handle.state::<PermissionsManager>();
+    let interval = Duration::from_millis(interval.unwrap_or(manager.config().watch_interval));
+
+    manager
+        .watchers()
+        .watch(app_handle.clone(), kind, interval, targets)
+}
+
+/// Stream permission statuses over a channel, scaling better than events with many windows.
+///
+/// Sends a snapshot of every subscribed permission first, then each change.
+///
+/// # Arguments
+/// - `channel`: The channel receiving the updates.
+/// - `kinds`: The permissions to subscribe to, defaults to all of them.
+/// - `interval`: The polling interval in milliseconds, defaults to the `watchInterval` config or `1000`.
+///
+/// # Returns
+/// - `u32`: The subscription id, pass it to `unwatch` to stop the updates.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::subscribe_permission_updates;
+///
+/// let id = subscribe_permission_updates(app_handle, channel, None, None).await;
+/// println!("Subscription: {}", id); // 2
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn subscribe_permission_updates<R: Runtime>(
+    app_handle: AppHandle<R>,
+    channel: Channel<PermissionUpdate>,
+    kinds: Option<Vec<PermissionKind>>,
+    interval: Option<u64>,
+) -> u32 {
+    let manager = app_handle.state::<PermissionsManager>();
+    let interval = Duration::from_millis(interval.unwrap_or(manager.config().watch_interval));
+    let kinds = kinds.unwrap_or_else(|| PermissionKind::ALL.to_vec());
+
+    manager
+        .watchers()
+        .subscribe(app_handle.clone(), channel, kinds, interval)
+}
+
+/// Stop a permission watcher or subscription.
+///
+/// # Returns
+/// - `bool`: `true` if the watcher was stopped, `false` if it does not exist.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::unwatch;
+///
+/// let stopped = unwatch(app_handle, id).await;
+/// println!("Stopped: {}", stopped); // true
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn unwatch<R: Runtime>(app_handle: AppHandle<R>, id: u32) -> bool {
+    app_handle
+        .state::<PermissionsManager>()
+        .watchers()
+        .unwatch(id)
+}
+
+/// Cancel a wait started with a `cancel_id`, like `open_settings_and_wait`.
+///
+/// # Returns
+/// - `bool`: `true` if the wait was cancelled, `false` if it is not running.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::cancel;
+///
+/// let cancelled = cancel(app_handle, 7).await;
+/// println!("Cancelled: {}", cancelled); // true
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn cancel<R: Runtime>(app_handle: AppHandle<R>, id: u32) -> bool {
+    app_handle
+        .state::<PermissionsManager>()
+        .watchers()
+        .cancel(id)
+}
+
+/// Check the status of a permission.
+///
+/// # Arguments
+/// - `kind`: The permission to check.
+/// - `details`: Whether to include the raw values the status was derived from, defaults to `false`.
+///
+/// # Returns
+/// - `PermissionState`: The status of the permission, with the raw values if requested. For microphone
+///   and camera it also reports whether a capture device is connected, to tell "denied" from "unplugged".
+///   On managed machines it reports whether a configuration profile controls the permission.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{check_permission, PermissionKind};
+///
+/// let state = check_permission(app_handle, PermissionKind::Camera, Some(true)).await;
+/// println!("Status: {:?}", state.status); // NotDetermined
+/// ```
+#[command]
+#[cfg_attr(feature = "specta", specta::specta)]
+pub async fn check_permission<R: Runtime>(
+    app_handle: AppHandle<R>,
+    kind: PermissionKind,
+    details: Option<bool>,
+) -> PermissionState {
+    let (status, raw_details) = app_handle
+        .state::<PermissionsManager>()
+        .probe(&app_handle, kind);
+
+    PermissionState {
+        kind,
+        status,
+        device_connected: device_connected(kind),
+        managed: managed(kind),
+        details: details.unwrap_or_default().then_some(raw_details),
+    }
+}
+
+/// How request commands ask for `kind`, see the `requestStrategies` config.
+#[cfg(target_os = "macos")]
+fn request_strategy<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+) -> RequestStrategy {
+    app_handle
+        .state::<PermissionsManager>()
+        .config()
+        .request_strategy(kind)
+}
+
+/// Whether the request of `kind` may open System Settings, see the `headlessRequests` and `requestStrategies` configs.
+#[cfg(target_os = "macos")]
+fn may_open_settings<R: Runtime>(app_handle: &AppHandle<R>, kind: PermissionKind) -> bool {
+    request_strategy(app_handle, kind) != RequestStrategy::Prompt
+}
+
+/// Show the priming dialog of `kind`, resolving with whether the user wants to continue.
+#[cfg(target_os = "macos")]
+async fn prime<R: Runtime>(app_handle: &AppHandle<R>, kind: PermissionKind) -> Result<bool, Error> {
+    let title = format!("Allow {}", kind.label());
+    let message = app_handle
+        .state::<PermissionsManager>()
+        .config()
+        .priming_messages
+        .get(&kind)
+        .cloned()
+        .unwrap_or_else(|| {
+            format!(
+                "Some features need {} permission, macOS asks you to allow it next.",
+                kind.label()
+            )
+        });
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+
+    app_handle
+        .run_on_main_thread(move || {
+            let _ = sender.send(run_priming_alert(&title, &message));
+        })
+        .map_err(|error| error.to_string())?;
+
+    let confirmed = receiver.await.map_err(|error| error.to_string())??;
+
+    Ok(confirmed)
+}
+
+/// Run before a request of `kind` that may show a prompt, resolving with the outcome if it must not be shown.
+///
+/// Resolves with `RemoteSession` if the user may not see the prompt, see `check_remote_session`.
+/// Waits while the session is locked or the screen saver runs, the prompt would be missed behind it.
+/// Then applies the request strategy of `kind`, see the `requestStrategies` config.
+///
+/// Fails with [`Error::NoGuiSession`] first, the strategies may open System Settings or a dialog.
+#[cfg(target_os = "macos")]
+async fn prepare_request<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+) -> Result<Option<RequestOutcome>, Error> {
+    ensure_gui_session()?;
+
+    if kind.check_blocking(app_handle) {
+        return Ok(None);
+    }
+
+    let strategy = request_strategy(app_handle, kind);
+
+    if strategy == RequestStrategy::Silent {
+        return Ok(Some(RequestOutcome::PromptUnavailable));
+    }
+
+    if remote_session().is_some() {
+        return Ok(Some(RequestOutcome::RemoteSession));
+    }
+
+    while !is_user_present() {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    match strategy {
+        RequestStrategy::Settings => {
+            let manager = app_handle.state::<PermissionsManager>();
+
+            manager.open_settings(kind)?;
+
+            manager.await_grant(kind);
+
+            Ok(Some(RequestOutcome::OpenedSettings))
+        }
+        RequestStrategy::Priming if !prime(app_handle, kind).await? => {
+            Ok(Some(RequestOutcome::Dismissed))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Run the request of `kind` once for all callers, after [`prepare_request`] and within its timeout.
+///
+/// The timeout covers the wait for the user to unlock the session as well, and dropping the
+/// returned future, as `cancel` does with `ensure_permission`, stops it.
+#[cfg(target_os = "macos")]
+async fn run_request<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+    request: impl std::future::Future<Output = Result<RequestOutcome, Error>>,
+) -> Result<RequestOutcome, Error> {
+    let manager = app_handle.state::<PermissionsManager>();
+
+    manager
+        .coordinate(
+            app_handle,
+            kind,
+            with_timeout(app_handle, kind, async {
+                if let Some(outcome) = prepare_request(app_handle, kind).await? {
+                    return Ok(outcome);
+                }
+
+                request.await
+            }),
+        )
+        .await
+}
+
+/// Run a request of `kind`, failing with [`Error::TimedOut`] past its configured timeout.
+#[cfg(target_os = "macos")]
+async fn with_timeout<R: Runtime, T>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+    request: impl std::future::Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    let Some(timeout) = app_handle
+        .state::<PermissionsManager>()
+        .config()
+        .request_timeout(kind)
+    else {
+        return request.await;
+    };
 
-    Ok(())
+    tokio::time::timeout(timeout, request)
+        .await
+        .map_err(|_| Error::TimedOut(kind))?
 }
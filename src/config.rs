@@ -0,0 +1,289 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde::Deserialize;
+
+use crate::models::PermissionKind;
+
+/// The prefix of the default event names, e.g. `macos-permissions://permission-changed`.
+const DEFAULT_EVENT_PREFIX: &str = "macos-permissions";
+
+/// Plugin configuration, read from `plugins > macos-permissions` in `tauri.conf.json`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Config {
+    /// The default polling interval of `watch_permission` in milliseconds. Defaults to `1000`.
+    pub watch_interval: u64,
+    /// The permissions the app needs, used by the onboarding window, the tray submenu and the reminders. Defaults to all of them.
+    pub permissions: Vec<PermissionKind>,
+    /// Remind the user about missing permissions, disabled when not set.
+    pub reminders: Option<ReminderConfig>,
+    /// Check that files dropped on the app's windows can be read, disabled when not set.
+    pub file_drop: Option<FileDropConfig>,
+    /// Serve the helper processes of the app on a unix socket, disabled when not set.
+    pub ipc: Option<IpcConfig>,
+    /// How long request commands wait for the user in milliseconds, without limit when not set.
+    pub request_timeout: Option<u64>,
+    /// Per permission overrides of `request_timeout`.
+    pub request_timeouts: HashMap<PermissionKind, u64>,
+    /// Never let request commands open System Settings, they only show the native prompts. Defaults to `false`.
+    pub headless_requests: bool,
+    /// Per permission overrides of how request commands ask for it. Defaults to `default` for all of them.
+    pub request_strategies: HashMap<PermissionKind, RequestStrategy>,
+    /// The messages of the dialogs shown by the `priming` request strategy, a generic one when not set.
+    pub priming_messages: HashMap<PermissionKind, String>,
+    /// Bring the main window back to the front once a permission the user was sent to System Settings for is granted. Defaults to `false`.
+    pub refocus_on_grant: bool,
+    /// Collect the changes detected by watchers for this many milliseconds before emitting them, disabled when not set.
+    pub coalesce_window: Option<u64>,
+    /// Replaces `macos-permissions` in the names of the emitted events, e.g. `acme-permissions://permission-changed`. Defaults to `macos-permissions`.
+    pub event_prefix: String,
+    /// The labels of the windows and webviews receiving the emitted events, all of them when empty. Defaults to none.
+    pub event_targets: Vec<String>,
+}
+
+impl Config {
+    /// How long the request of `kind` may wait for the user.
+    pub fn request_timeout(&self, kind: PermissionKind) -> Option<Duration> {
+        self.request_timeouts
+            .get(&kind)
+            .copied()
+            .or(self.request_timeout)
+            .map(Duration::from_millis)
+    }
+
+    /// How request commands ask for `kind`, `prompt` instead of `default` and `priming` with `headless_requests`.
+    pub fn request_strategy(&self, kind: PermissionKind) -> RequestStrategy {
+        match self
+            .request_strategies
+            .get(&kind)
+            .copied()
+            .unwrap_or_default()
+        {
+            // Both may show a dialog or open System Settings, which headless requests never do.
+            RequestStrategy::Default | RequestStrategy::Priming if self.headless_requests => {
+                RequestStrategy::Prompt
+            }
+            strategy => strategy,
+        }
+    }
+
+    /// The name `event` is emitted under, with the `event_prefix` applied.
+    pub fn event_name(&self, event: &str) -> String {
+        match event.strip_prefix(DEFAULT_EVENT_PREFIX) {
+            Some(name) => format!("{}{name}", self.event_prefix),
+            None => event.to_string(),
+        }
+    }
+
+    /// Check the values that would only fail later, when they are used.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        // The characters Tauri accepts in event names.
+        let valid = |c: char| c.is_alphanumeric() || matches!(c, '-' | '/' | ':' | '_');
+
+        if self.event_prefix.is_empty() || !self.event_prefix.chars().all(valid) {
+            return Err(format!("Invalid eventPrefix {:?}", self.event_prefix));
+        }
+
+        if self.headless_requests {
+            if let Some((kind, strategy)) = self.request_strategies.iter().find(|(_, &strategy)| {
+                matches!(
+                    strategy,
+                    RequestStrategy::Settings | RequestStrategy::Priming
+                )
+            }) {
+                return Err(format!(
+                    "requestStrategies {strategy:?} for {kind:?} shows UI, which headlessRequests forbids"
+                ));
+            }
+        }
+
+        // The reminders would be checked in a busy loop.
+        if self
+            .reminders
+            .as_ref()
+            .is_some_and(|reminders| reminders.interval == 0)
+        {
+            return Err("reminders interval must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// How long watchers collect changes before emitting them.
+    pub fn coalesce_window(&self) -> Option<Duration> {
+        self.coalesce_window.map(Duration::from_millis)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            watch_interval: 1000,
+            permissions: PermissionKind::ALL.to_vec(),
+            reminders: None,
+            file_drop: None,
+            ipc: None,
+            request_timeout: None,
+            request_timeouts: HashMap::new(),
+            headless_requests: false,
+            request_strategies: HashMap::new(),
+            priming_messages: HashMap::new(),
+            refocus_on_grant: false,
+            coalesce_window: None,
+            event_prefix: DEFAULT_EVENT_PREFIX.to_string(),
+            event_targets: Vec::new(),
+        }
+    }
+}
+
+/// How a request command asks for a permission, read from `requestStrategies` in the plugin configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RequestStrategy {
+    /// Show the native prompt, or open System Settings for the permissions without one.
+    #[default]
+    Default,
+    /// Only show the native prompt, never open System Settings, like `headlessRequests` for a single permission.
+    Prompt,
+    /// Open System Settings right away, without showing the native prompt.
+    Settings,
+    /// Explain why the permission is needed in a dialog first, then continue like `default` unless the user declines.
+    Priming,
+    /// Never show anything, the request resolves with `Granted` or `PromptUnavailable`.
+    Silent,
+}
+
+/// When to remind the user about missing permissions, read from `reminders` in the plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReminderConfig {
+    /// The delay before the first reminder in milliseconds. Defaults to one hour.
+    pub interval: u64,
+    /// The factor the delay grows by after each reminder. Defaults to `2`.
+    pub backoff: f64,
+    /// The longest delay between two reminders in milliseconds. Defaults to one week.
+    pub max_interval: u64,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        Self {
+            interval: 60 * 60 * 1000,
+            backoff: 2.0,
+            max_interval: 7 * 24 * 60 * 60 * 1000,
+        }
+    }
+}
+
+/// What to do with dropped files, read from `fileDrop` in the plugin configuration.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FileDropConfig {
+    /// Request the missing access when a dropped file cannot be read, opening System Settings for
+    /// full disk access or Files & Folders. Defaults to `false`.
+    pub request: bool,
+}
+
+/// Where helper processes reach the app, read from `ipc` in the plugin configuration.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct IpcConfig {
+    /// The path of the unix socket. Defaults to `macos-permissions.sock` in the app data directory.
+    pub path: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn config(value: serde_json::Value) -> Config {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn request_strategy_defaults_to_default() {
+        let config = config(json!({ "requestStrategies": { "camera": "settings" } }));
+
+        assert_eq!(
+            config.request_strategy(PermissionKind::Camera),
+            RequestStrategy::Settings
+        );
+        assert_eq!(
+            config.request_strategy(PermissionKind::Microphone),
+            RequestStrategy::Default
+        );
+    }
+
+    #[test]
+    fn request_strategy_only_prompts_with_headless_requests() {
+        let config = config(json!({
+            "headlessRequests": true,
+            "requestStrategies": { "camera": "priming", "microphone": "prompt" },
+        }));
+
+        for kind in PermissionKind::ALL {
+            assert_eq!(config.request_strategy(kind), RequestStrategy::Prompt);
+        }
+    }
+
+    #[test]
+    fn validate_accepts_the_defaults() {
+        assert_eq!(Config::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_event_prefixes() {
+        for prefix in ["", "acme permissions", "acme.permissions"] {
+            assert!(config(json!({ "eventPrefix": prefix })).validate().is_err());
+        }
+
+        assert_eq!(
+            config(json!({ "eventPrefix": "acme-permissions" })).validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_rejects_strategies_showing_ui_with_headless_requests() {
+        for strategy in ["settings", "priming"] {
+            let config = config(json!({
+                "headlessRequests": true,
+                "requestStrategies": { "camera": strategy },
+            }));
+
+            assert!(config.validate().is_err());
+        }
+
+        let config = config(json!({
+            "headlessRequests": true,
+            "requestStrategies": { "camera": "prompt" },
+        }));
+
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_a_reminders_interval_of_zero() {
+        assert!(config(json!({ "reminders": { "interval": 0 } }))
+            .validate()
+            .is_err());
+        assert_eq!(config(json!({ "reminders": {} })).validate(), Ok(()));
+    }
+
+    #[test]
+    fn event_name_replaces_the_default_prefix() {
+        let config = config(json!({ "eventPrefix": "acme-permissions" }));
+
+        assert_eq!(
+            config.event_name("macos-permissions://reminder"),
+            "acme-permissions://reminder"
+        );
+        assert_eq!(
+            Config::default().event_name("macos-permissions://reminder"),
+            "macos-permissions://reminder"
+        );
+        assert_eq!(config.event_name("other://reminder"), "other://reminder");
+    }
+}
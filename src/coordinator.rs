@@ -0,0 +1,170 @@
+use std::{
+    collections::HashMap,
+    fs,
+    future::Future,
+    path::PathBuf,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime},
+};
+
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::watch;
+
+use crate::{
+    error::Error,
+    manager::PermissionsManager,
+    models::{PermissionKind, RequestOutcome},
+};
+
+type Outcome = Option<Result<RequestOutcome, Error>>;
+
+/// Runs one request per permission at a time, the callers coming in meanwhile share its outcome.
+///
+/// Without it, two windows requesting together show the prompt twice or open System Settings twice.
+#[derive(Default)]
+pub(crate) struct Coordinator {
+    pending: Mutex<HashMap<PermissionKind, watch::Receiver<Outcome>>>,
+}
+
+impl Coordinator {
+    /// Run `request` unless `kind` is already requested, then resolve with the outcome of that request.
+    ///
+    /// Other instances of the app are waited for as well, see [`lock_across_processes`].
+    pub(crate) async fn run<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        kind: PermissionKind,
+        request: impl Future<Output = Result<RequestOutcome, Error>>,
+    ) -> Result<RequestOutcome, Error> {
+        let leader = {
+            let mut pending = self.pending.lock().unwrap();
+
+            match pending.get(&kind) {
+                Some(receiver) => Err(receiver.clone()),
+                None => {
+                    let (sender, receiver) = watch::channel(None);
+
+                    pending.insert(kind, receiver);
+
+                    Ok(sender)
+                }
+            }
+        };
+
+        let sender = match leader {
+            Ok(sender) => sender,
+            Err(mut receiver) => {
+                // The sender is dropped without an outcome if the leading request was, e.g. with its window.
+                return match receiver.wait_for(Option::is_some).await {
+                    Ok(outcome) => outcome.clone().unwrap_or(Err(Error::Cancelled(kind))),
+                    Err(_) => Err(Error::Cancelled(kind)),
+                };
+            }
+        };
+
+        let _pending = Pending {
+            coordinator: self,
+            kind,
+        };
+
+        let outcome = match lock_across_processes(app_handle, kind).await {
+            Ok((_, Some(shared))) => Ok(shared),
+            Ok((lock, None)) => {
+                let outcome = request.await;
+
+                if let (Some(lock), Ok(outcome)) = (&lock, &outcome) {
+                    lock.share(*outcome);
+                }
+
+                outcome
+            }
+            Err(error) => Err(error),
+        };
+
+        let _ = sender.send(Some(outcome.clone()));
+
+        outcome
+    }
+}
+
+/// Forgets the request of `kind` once it finished or was dropped.
+struct Pending<'a> {
+    coordinator: &'a Coordinator,
+    kind: PermissionKind,
+}
+
+impl Drop for Pending<'_> {
+    fn drop(&mut self) {
+        self.coordinator.pending.lock().unwrap().remove(&self.kind);
+    }
+}
+
+/// Held while this instance of the app requests a permission, with the file the outcome is shared through.
+struct ProcessLock {
+    path: PathBuf,
+    _file: fs::File,
+}
+
+impl ProcessLock {
+    /// Leave `outcome` to the instances waiting for the lock.
+    fn share(&self, outcome: RequestOutcome) {
+        if let Ok(bytes) = serde_json::to_vec(&outcome) {
+            let _ = fs::write(&self.path, bytes);
+        }
+    }
+}
+
+/// Wait until no other instance of the app requests `kind`, through a lock file in the app data directory.
+///
+/// Returns the outcome an instance shared while this one waited, the request must not run again then.
+/// Fails with [`Error::TimedOut`] once the request timeout of `kind` passed while waiting.
+async fn lock_across_processes<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+) -> Result<(Option<ProcessLock>, Option<RequestOutcome>), Error> {
+    let Ok(dir) = app_handle.path().app_data_dir() else {
+        return Ok((None, None));
+    };
+
+    let _ = fs::create_dir_all(&dir);
+
+    let path = dir.join(format!("macos-permissions-{kind:?}.lock"));
+    let started = SystemTime::now();
+    let deadline = app_handle
+        .state::<PermissionsManager>()
+        .config()
+        .request_timeout(kind)
+        .map(|timeout| Instant::now() + timeout);
+    let mut waited = false;
+
+    loop {
+        match crate::macos::try_lock_file(&path) {
+            Ok(Some(file)) => {
+                // Only an outcome written while waiting is about the same prompt.
+                let shared = waited
+                    .then(|| {
+                        let modified = file.metadata().and_then(|metadata| metadata.modified());
+
+                        if modified.map_or(true, |modified| modified < started) {
+                            return None;
+                        }
+
+                        serde_json::from_slice(&fs::read(&path).ok()?).ok()
+                    })
+                    .flatten();
+
+                return Ok((Some(ProcessLock { path, _file: file }), shared));
+            }
+            Ok(None) => {
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    return Err(Error::TimedOut(kind));
+                }
+
+                waited = true;
+
+                tokio::time::sleep(Duration::from_millis(250)).await;
+            }
+            Err(_) => return Ok((None, None)),
+        }
+    }
+}
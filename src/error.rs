@@ -0,0 +1,92 @@
+use serde::{Serialize, Serializer};
+
+use crate::models::PermissionKind;
+
+/// Errors returned by [`require_permission`](crate::require_permission) and the commands.
+///
+/// Serialized as `{ code, message, permission }` so the frontend can tell them apart by their stable `code`.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?} permission is not granted")]
+    PermissionDenied(PermissionKind),
+    #[error("{0}")]
+    Request(String),
+    #[error("{0:?} permission request timed out")]
+    TimedOut(PermissionKind),
+    #[error("{0:?} permission request was cancelled")]
+    Cancelled(PermissionKind),
+    #[error("{0:?} permission prompt is no longer available")]
+    PromptUnavailable(PermissionKind),
+    #[error("No GUI session is available to present the request")]
+    NoGuiSession,
+    #[error("{0} is not a TCC service identifier")]
+    InvalidService(String),
+    #[error("Cancel id {0} is already in use")]
+    CancelIdInUse(u32),
+    #[error("{0}")]
+    Unsupported(&'static str),
+}
+
+impl Error {
+    /// A stable identifier of the error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::PermissionDenied(_) => "permissionDenied",
+            Error::Request(_) => "requestFailed",
+            Error::TimedOut(_) => "timedOut",
+            Error::Cancelled(_) => "cancelled",
+            Error::PromptUnavailable(_) => "promptUnavailable",
+            Error::NoGuiSession => "noGuiSession",
+            Error::InvalidService(_) => "invalidService",
+            Error::CancelIdInUse(_) => "cancelIdInUse",
+            Error::Unsupported(_) => "unsupported",
+        }
+    }
+
+    /// The permission the error is about.
+    pub fn permission(&self) -> Option<PermissionKind> {
+        match self {
+            Error::PermissionDenied(kind)
+            | Error::TimedOut(kind)
+            | Error::Cancelled(kind)
+            | Error::PromptUnavailable(kind) => Some(*kind),
+            Error::Request(_)
+            | Error::NoGuiSession
+            | Error::InvalidService(_)
+            | Error::CancelIdInUse(_)
+            | Error::Unsupported(_) => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type), specta(remote = Error, rename = "PermissionError"))]
+struct ErrorPayload {
+    code: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permission: Option<PermissionKind>,
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ErrorPayload {
+            code: self.code(),
+            message: self.to_string(),
+            permission: self.permission(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Request(message)
+    }
+}
+
+impl From<Error> for String {
+    fn from(error: Error) -> Self {
+        error.to_string()
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use tauri::{AppHandle, DragDropEvent, Manager, RunEvent, Runtime, WebviewEvent, WindowEvent};
+
+use crate::{
+    config::FileDropConfig,
+    manager::PermissionsManager,
+    models::{FileDropResolved, PathAccess, PathAccessEntry, PermissionKind, RequestOutcome},
+    prompt::prompting,
+};
+
+/// Emitted once the access of files dropped on a window was checked, with a [`FileDropResolved`] payload.
+pub const FILE_DROP_RESOLVED_EVENT: &str = "macos-permissions://file-drop-resolved";
+
+/// `EPERM`, returned by TCC and the sandbox, while file system permissions fail with `EACCES`.
+#[cfg(target_os = "macos")]
+const EPERM: i32 = 1;
+
+/// Check the files dropped on a window or webview when the `fileDrop` config is set, then emit [`FILE_DROP_RESOLVED_EVENT`].
+pub(crate) fn on_event<R: Runtime>(app_handle: &AppHandle<R>, event: &RunEvent) {
+    let (label, paths) = match event {
+        RunEvent::WindowEvent {
+            label,
+            event: WindowEvent::DragDrop(DragDropEvent::Drop { paths, .. }),
+            ..
+        }
+        | RunEvent::WebviewEvent {
+            label,
+            event: WebviewEvent::DragDrop(DragDropEvent::Drop { paths, .. }),
+            ..
+        } => (label.clone(), paths.clone()),
+        _ => return,
+    };
+
+    let Some(config) = app_handle
+        .try_state::<PermissionsManager>()
+        .and_then(|manager| manager.config().file_drop.clone())
+    else {
+        return;
+    };
+
+    let app_handle = app_handle.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let home_dir = app_handle.path().home_dir().ok();
+
+        // Reading waits for the Files & Folders prompt if the user was not asked yet.
+        let paths =
+            tauri::async_runtime::spawn_blocking(move || check_paths(home_dir.as_deref(), &paths))
+                .await
+                .unwrap_or_default();
+
+        let outcome = request_access(&app_handle, &config, &paths).await;
+
+        app_handle.state::<PermissionsManager>().emit(
+            &app_handle,
+            FILE_DROP_RESOLVED_EVENT,
+            FileDropResolved {
+                label,
+                ready: paths
+                    .iter()
+                    .all(|entry| entry.access == PathAccess::Readable),
+                paths,
+                outcome,
+            },
+        );
+    });
+}
+
+/// Read each of `paths`, classifying the access that is missing when it fails.
+///
+/// Paths in the folders protected by Files & Folders show its prompt if the user was not asked yet.
+pub(crate) fn check_paths(home_dir: Option<&Path>, paths: &[PathBuf]) -> Vec<PathAccessEntry> {
+    paths
+        .iter()
+        .map(|path| PathAccessEntry {
+            path: path.to_string_lossy().into_owned(),
+            access: access(home_dir, path),
+        })
+        .collect()
+}
+
+fn access(home_dir: Option<&Path>, path: &Path) -> PathAccess {
+    // Shows the Files & Folders prompt if the user was not asked yet.
+    let read = prompting(|| {
+        fs::metadata(path).and_then(|metadata| {
+            if metadata.is_dir() {
+                fs::read_dir(path).map(drop)
+            } else {
+                fs::File::open(path)?.read(&mut [0; 1]).map(drop)
+            }
+        })
+    });
+
+    match read {
+        Ok(()) => PathAccess::Readable,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => PathAccess::Missing,
+        #[cfg(target_os = "macos")]
+        Err(error) if error.raw_os_error() == Some(EPERM) => {
+            blocked_by(home_dir, path, crate::macos::is_sandboxed())
+        }
+        Err(_) => {
+            let _ = home_dir;
+
+            PathAccess::Denied
+        }
+    }
+}
+
+/// Which protection rejected reading `path`, TCC only reports `EPERM`.
+#[cfg(any(target_os = "macos", test))]
+fn blocked_by(home_dir: Option<&Path>, path: &Path, sandboxed: bool) -> PathAccess {
+    if sandboxed {
+        return PathAccess::Sandbox;
+    }
+
+    // Reference: https://support.apple.com/guide/mac-help/control-access-to-files-and-folders-on-mac-mchld5a35146
+    let files_and_folders = home_dir
+        .into_iter()
+        .flat_map(|home_dir| {
+            [
+                "Desktop",
+                "Documents",
+                "Downloads",
+                "Library/Mobile Documents",
+            ]
+            .map(|folder| home_dir.join(folder))
+        })
+        .chain([PathBuf::from("/Volumes")]);
+
+    for folder in files_and_folders {
+        if path.starts_with(folder) {
+            return PathAccess::FilesAndFolders;
+        }
+    }
+
+    PathAccess::FullDiskAccess
+}
+
+/// Request the access missing for `paths` with the `request` option of the `fileDrop` config.
+///
+/// Full disk access covers every path, it is requested first.
+async fn request_access<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    config: &FileDropConfig,
+    paths: &[PathAccessEntry],
+) -> Option<RequestOutcome> {
+    if !config.request {
+        return None;
+    }
+
+    let missing = |access| paths.iter().any(|entry| entry.access == access);
+
+    if missing(PathAccess::FullDiskAccess) {
+        return PermissionKind::FullDiskAccess
+            .request(app_handle)
+            .await
+            .ok();
+    }
+
+    #[cfg(target_os = "macos")]
+    if missing(PathAccess::FilesAndFolders) {
+        let manager = app_handle.state::<PermissionsManager>();
+
+        // The prompt was already answered when the path was read, the user can only change it in System Settings.
+        if manager.config().headless_requests {
+            return Some(RequestOutcome::PromptUnavailable);
+        }
+
+        return match manager.open_privacy_settings("Privacy_FilesAndFolders") {
+            Ok(()) => Some(RequestOutcome::OpenedSettings),
+            Err(crate::error::Error::NoGuiSession) => Some(RequestOutcome::PromptUnavailable),
+            Err(_) => None,
+        };
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocked_by_tells_files_and_folders_from_full_disk_access() {
+        let home_dir = Path::new("/Users/me");
+        let blocked_by = |path: &str| blocked_by(Some(home_dir), Path::new(path), false);
+
+        for path in [
+            "/Users/me/Desktop/notes.txt",
+            "/Users/me/Documents",
+            "/Users/me/Downloads/archive.zip",
+            "/Users/me/Library/Mobile Documents/com~apple~CloudDocs/notes.txt",
+            "/Volumes/Backup/notes.txt",
+        ] {
+            assert_eq!(blocked_by(path), PathAccess::FilesAndFolders, "{path}");
+        }
+
+        for path in [
+            "/Users/me/Library/Mail",
+            "/Users/me/DesktopBackup/notes.txt",
+            "/Users/other/Desktop/notes.txt",
+        ] {
+            assert_eq!(blocked_by(path), PathAccess::FullDiskAccess, "{path}");
+        }
+    }
+
+    #[test]
+    fn blocked_by_reports_the_sandbox_first() {
+        let path = Path::new("/Users/me/Desktop/notes.txt");
+
+        assert_eq!(
+            blocked_by(Some(Path::new("/Users/me")), path, true),
+            PathAccess::Sandbox
+        );
+    }
+
+    #[test]
+    fn blocked_by_needs_the_home_dir_for_its_folders() {
+        assert_eq!(
+            blocked_by(None, Path::new("/Users/me/Desktop/notes.txt"), false),
+            PathAccess::FullDiskAccess
+        );
+        assert_eq!(
+            blocked_by(None, Path::new("/Volumes/Backup"), false),
+            PathAccess::FilesAndFolders
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tauri::{ipc::Invoke, Manager, Runtime};
+
+use crate::{
+    error::Error,
+    manager::PermissionsManager,
+    models::{PermissionKind, RequestOutcome, StatusSummary},
+    onboarding,
+};
+
+/// Fail with [`Error::PermissionDenied`] unless the permission is granted.
+///
+/// Fails with [`Error::PromptUnavailable`] instead if the request could not show a prompt.
+///
+/// Used by the `#[requires_permission]` attribute, it can also be called directly from app code.
+///
+/// # Arguments
+/// - `manager`: The `AppHandle`, or any window or webview.
+/// - `kind`: The required permission.
+/// - `request`: Request the permission if it is not granted, then check again.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{require_permission, PermissionKind};
+///
+/// require_permission(&app_handle, PermissionKind::Microphone, true).await?;
+/// ```
+pub async fn require_permission<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    kind: PermissionKind,
+    request: bool,
+) -> Result<(), Error> {
+    let app_handle = manager.app_handle();
+
+    if kind.check(app_handle).await {
+        return Ok(());
+    }
+
+    if request {
+        match kind.request(app_handle).await? {
+            RequestOutcome::Granted => return Ok(()),
+            RequestOutcome::PromptUnavailable => return Err(Error::PromptUnavailable(kind)),
+            _ => {}
+        }
+    }
+
+    Err(Error::PermissionDenied(kind))
+}
+
+/// Check the permissions an app requires at launch, usually from its setup hook.
+///
+/// Prefer the [`ensure_permissions!`](crate::ensure_permissions) macro, which lists the kinds without the enum path.
+///
+/// # Arguments
+/// - `manager`: The app, or any window or webview.
+/// - `kinds`: The required permissions.
+/// - `onboarding`: Open the onboarding window listing the missing permissions, if any.
+///
+/// # Returns
+/// - `StatusSummary`: The status of `kinds`, use [`StatusSummary::is_granted`] to gate the features that need them.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{ensure_permissions, PermissionKind};
+///
+/// let summary = ensure_permissions(app, &[PermissionKind::Accessibility], true)?;
+/// println!("Ok: {}", summary.ok); // false
+/// ```
+pub fn ensure_permissions<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    kinds: &[PermissionKind],
+    onboarding: bool,
+) -> tauri::Result<StatusSummary> {
+    let app_handle = manager.app_handle();
+
+    let summary = app_handle
+        .state::<PermissionsManager>()
+        .summary(app_handle, kinds);
+
+    if onboarding && !summary.ok {
+        onboarding::open(app_handle, summary.missing.clone())?;
+    }
+
+    Ok(summary)
+}
+
+/// Check the permissions an app requires at launch, see [`ensure_permissions`](fn@crate::ensure_permissions).
+///
+/// Append `onboarding` to open the onboarding window when any is missing.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{ensure_permissions, PermissionKind};
+///
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_macos_permissions::init())
+///     .setup(|app| {
+///         let summary = ensure_permissions!(app, [Accessibility, ScreenRecording], onboarding)?;
+///
+///         if summary.is_granted(PermissionKind::ScreenRecording) {
+///             start_capture(app.handle());
+///         }
+///
+///         Ok(())
+///     })
+/// ```
+#[macro_export]
+macro_rules! ensure_permissions {
+    ($manager:expr, [$($kind:ident),* $(,)?]) => {
+        $crate::ensure_permissions(
+            $manager,
+            &[$($crate::PermissionKind::$kind),*],
+            false,
+        )
+    };
+    ($manager:expr, [$($kind:ident),* $(,)?], onboarding) => {
+        $crate::ensure_permissions(
+            $manager,
+            &[$($crate::PermissionKind::$kind),*],
+            true,
+        )
+    };
+}
+
+/// Rejects invokes of the app's own commands unless the permissions they require are granted.
+///
+/// The rejection is an [`Error`], serialized as `{ code, message, permission }`.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{PermissionGate, PermissionKind};
+///
+/// let gate = PermissionGate::new()
+///     .require("start_recording", PermissionKind::Microphone)
+///     .require_or_request("paste", PermissionKind::Accessibility);
+///
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_macos_permissions::init())
+///     .invoke_handler(gate.wrap(tauri::generate_handler![start_recording, paste]))
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PermissionGate {
+    rules: HashMap<String, (PermissionKind, bool)>,
+}
+
+impl PermissionGate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject `command` unless `kind` is granted.
+    pub fn require(mut self, command: impl Into<String>, kind: PermissionKind) -> Self {
+        self.rules.insert(command.into(), (kind, false));
+        self
+    }
+
+    /// Request `kind` before `command` runs if it is not granted, rejecting if it is still missing.
+    pub fn require_or_request(mut self, command: impl Into<String>, kind: PermissionKind) -> Self {
+        self.rules.insert(command.into(), (kind, true));
+        self
+    }
+
+    /// Wrap the app's invoke handler, usually `tauri::generate_handler![...]`.
+    pub fn wrap<R, F>(self, handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+    where
+        R: Runtime,
+        F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+
+        move |invoke: Invoke<R>| {
+            let Some(&(kind, request)) = self.rules.get(invoke.message.command()) else {
+                return handler(invoke);
+            };
+
+            let handler = handler.clone();
+
+            tauri::async_runtime::spawn(async move {
+                let webview = invoke.message.webview();
+
+                if let Err(error) = require_permission(&webview, kind, request).await {
+                    return invoke.resolver.reject(error);
+                }
+
+                // Commands expect to be dispatched from the main thread, like without the gate.
+                let _ = webview.run_on_main_thread(move || {
+                    handler(invoke);
+                });
+            });
+
+            true
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::{fs::PermissionsExt, net},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt},
+    net::{UnixListener, UnixStream},
+};
+
+use crate::{
+    commands::open_onboarding,
+    config::IpcConfig,
+    error::Error,
+    manager::PermissionsManager,
+    models::{PermissionKind, PermissionStatus, RequestOutcome},
+};
+
+const FILE_NAME: &str = "macos-permissions.sock";
+
+/// A request of a helper process, sent as a line of JSON, e.g. `{"method":"status","kind":"camera"}`.
+///
+/// Each request is answered with a line of `{"ok":value}` or `{"error":{"code","message"}}`, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+pub enum IpcRequest {
+    /// Query the status of `kind`, answered with a `PermissionStatus`.
+    Status { kind: PermissionKind },
+    /// Query the status of `kinds`, the `permissions` config when not set, answered with a `StatusSummary`.
+    Summary { kinds: Option<Vec<PermissionKind>> },
+    /// Request `kind` in the app, answered with a `RequestOutcome`.
+    Request { kind: PermissionKind },
+    /// Open the System Settings pane of `kind`, answered with `null`.
+    OpenSettings { kind: PermissionKind },
+    /// Open the onboarding window for `kinds`, the `permissions` config when not set, answered with `null`.
+    Onboarding { kinds: Option<Vec<PermissionKind>> },
+}
+
+/// Listen on the socket of the `ipc` config, so helper processes share the permission logic of the app.
+///
+/// Leaves the socket to another instance of the app already listening on it.
+pub(crate) fn listen<R: Runtime>(app_handle: &AppHandle<R>, config: &IpcConfig) -> io::Result<()> {
+    let path = match &config.path {
+        Some(path) => PathBuf::from(path),
+        None => app_handle
+            .path()
+            .app_data_dir()
+            .map_err(io::Error::other)?
+            .join(FILE_NAME),
+    };
+
+    if net::UnixStream::connect(&path).is_ok() {
+        return Ok(());
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // Left behind by an instance that did not exit cleanly.
+    let _ = fs::remove_file(&path);
+
+    let listener = net::UnixListener::bind(&path)?;
+
+    // Only processes of the same user may connect.
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    listener.set_nonblocking(true)?;
+
+    let app_handle = app_handle.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let Ok(listener) = UnixListener::from_std(listener) else {
+            return;
+        };
+
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+
+                continue;
+            };
+
+            tauri::async_runtime::spawn(serve(app_handle.clone(), stream));
+        }
+    });
+
+    Ok(())
+}
+
+/// Answer the requests of a connected helper until it disconnects.
+async fn serve<R: Runtime>(app_handle: AppHandle<R>, stream: UnixStream) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = tokio::io::BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => match handle(&app_handle, request).await {
+                Ok(value) => json!({ "ok": value }),
+                Err(error) => json!({ "error": error }),
+            },
+            Err(error) => json!({ "error": Error::Request(format!("Invalid request: {error}")) }),
+        };
+
+        let mut bytes = response.to_string().into_bytes();
+
+        bytes.push(b'\n');
+
+        if writer.write_all(&bytes).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn handle<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    request: IpcRequest,
+) -> Result<Value, Error> {
+    let manager = app_handle.state::<PermissionsManager>();
+
+    let value = match request {
+        IpcRequest::Status { kind } => json!(manager.refresh(app_handle, kind)),
+        IpcRequest::Summary { kinds } => {
+            let kinds = kinds.unwrap_or_else(|| manager.config().permissions.clone());
+
+            json!(manager.summary(app_handle, &kinds))
+        }
+        IpcRequest::Request { kind } => json!(kind.request(app_handle).await?),
+        IpcRequest::OpenSettings { kind } => {
+            manager.open_settings(kind)?;
+
+            Value::Null
+        }
+        IpcRequest::Onboarding { kinds } => {
+            open_onboarding(app_handle.clone(), kinds).await?;
+
+            Value::Null
+        }
+    };
+
+    Ok(value)
+}
+
+/// A blocking connection of a helper process to the app, see the `ipc` config.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{IpcClient, PermissionKind};
+///
+/// let mut client = IpcClient::connect("/Users/me/Library/Application Support/com.example.app/macos-permissions.sock")?;
+///
+/// if !client.status(PermissionKind::Camera)?.is_granted() {
+///     let outcome = client.request(PermissionKind::Camera)?;
+///     println!("Outcome: {:?}", outcome); // Granted
+/// }
+/// ```
+pub struct IpcClient {
+    reader: BufReader<net::UnixStream>,
+    writer: net::UnixStream,
+}
+
+impl IpcClient {
+    /// Connect to the socket the app listens on.
+    pub fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        let writer = net::UnixStream::connect(path)?;
+
+        Ok(Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        })
+    }
+
+    /// Send `request` and wait for its answer, a rejection of the app fails with its message.
+    pub fn call(&mut self, request: &IpcRequest) -> io::Result<Value> {
+        let mut bytes = serde_json::to_vec(request)?;
+
+        bytes.push(b'\n');
+        self.writer.write_all(&bytes)?;
+
+        let mut line = String::new();
+
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let mut response: Value = serde_json::from_str(&line)?;
+
+        if let Some(error) = response.get("error") {
+            let message = error["message"].as_str().unwrap_or_default();
+
+            return Err(io::Error::other(message.to_string()));
+        }
+
+        Ok(response["ok"].take())
+    }
+
+    /// The status of `kind`, queried by the app.
+    pub fn status(&mut self, kind: PermissionKind) -> io::Result<PermissionStatus> {
+        Ok(serde_json::from_value(
+            self.call(&IpcRequest::Status { kind })?,
+        )?)
+    }
+
+    /// Request `kind` in the app, resolving once the user responded.
+    pub fn request(&mut self, kind: PermissionKind) -> io::Result<RequestOutcome> {
+        Ok(serde_json::from_value(
+            self.call(&IpcRequest::Request { kind })?,
+        )?)
+    }
+}
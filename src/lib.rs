@@ -1,28 +1,267 @@
 use tauri::{
     generate_handler,
     plugin::{Builder, TauriPlugin},
-    Runtime,
+    Manager, Runtime,
 };
 
 mod commands;
+mod config;
+#[cfg(target_os = "macos")]
+mod coordinator;
+mod error;
+mod file_drop;
+mod guard;
+#[cfg(unix)]
+mod ipc;
+mod location;
+#[cfg(target_os = "macos")]
+mod macos;
+mod manager;
+mod mobileconfig;
+mod models;
+#[cfg(target_os = "macos")]
+mod notifications;
+mod onboarding;
+mod pppc;
+mod privacy;
+mod prompt;
+mod reminders;
+mod status;
+#[cfg(all(target_os = "macos", feature = "swift"))]
+mod swift;
+#[cfg(target_os = "macos")]
+mod tcc;
+#[cfg(desktop)]
+mod tray;
+mod upgrade;
+#[cfg(target_os = "macos")]
+mod verify;
+mod watcher;
+mod webview;
 
 pub use commands::*;
+pub use config::{Config, FileDropConfig, IpcConfig, ReminderConfig, RequestStrategy};
+pub use error::Error;
+pub use file_drop::FILE_DROP_RESOLVED_EVENT;
+pub use guard::{ensure_permissions, require_permission, PermissionGate};
+#[cfg(unix)]
+pub use ipc::{IpcClient, IpcRequest};
+pub use location::Position;
+pub use manager::{PermissionsManager, GRANT_COMPLETED_EVENT};
+pub use models::*;
+pub use onboarding::ONBOARDING_FINISHED_EVENT;
+pub use pppc::PppcProfile;
+pub use privacy::{missing_privacy_entries, privacy_manifest};
+pub use reminders::{Reminders, REMINDER_EVENT};
+pub use status::{bundle_statuses, native_status};
+#[cfg(desktop)]
+pub use tray::status_menu;
+pub use upgrade::{OsUpgrade, OS_UPGRADED_EVENT};
+pub use watcher::{
+    Watchers, GLOBAL_SHORTCUT_READY_EVENT, PERMISSIONS_CHANGED_EVENT, PERMISSION_CHANGED_EVENT,
+};
+pub use webview::webview_permission_handler;
+
+#[cfg(feature = "macros")]
+pub use tauri_plugin_macos_permissions_macros::requires_permission;
 
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("macos-permissions")
+pub fn init<R: Runtime>() -> TauriPlugin<R, Option<Config>> {
+    Builder::<R, Option<Config>>::new("macos-permissions")
         .invoke_handler(generate_handler![
             commands::check_accessibility_permission,
             commands::request_accessibility_permission,
+            commands::open_accessibility_settings,
             commands::check_full_disk_access_permission,
             commands::request_full_disk_access_permission,
+            commands::open_full_disk_access_settings,
             commands::check_screen_recording_permission,
             commands::request_screen_recording_permission,
+            commands::open_screen_recording_settings,
             commands::check_microphone_permission,
             commands::request_microphone_permission,
+            commands::open_microphone_settings,
             commands::check_camera_permission,
             commands::request_camera_permission,
+            commands::open_camera_settings,
             commands::check_input_monitoring_permission,
-            commands::request_input_monitoring_permission
+            commands::request_input_monitoring_permission,
+            commands::open_input_monitoring_settings,
+            commands::check_location_permission,
+            commands::request_location_permission,
+            commands::open_location_settings,
+            commands::get_current_position,
+            commands::check_notification_permission,
+            commands::request_notification_permission,
+            commands::open_notification_settings,
+            commands::list_capture_devices,
+            commands::verify_microphone_access,
+            commands::verify_screen_recording_access,
+            commands::verify_accessibility_access,
+            commands::check_event_tap_readiness,
+            commands::check_profile,
+            commands::has_been_requested,
+            commands::get_prompt_history,
+            commands::generate_pppc_profile,
+            commands::check_app_location,
+            commands::get_app_identity,
+            commands::find_app_copies,
+            commands::get_responsible_process,
+            commands::check_remote_session,
+            commands::is_virtual_machine,
+            commands::is_sip_enabled,
+            commands::get_plugin_info,
+            commands::get_permission_stats,
+            commands::request_path_access,
+            commands::check_global_shortcut_readiness,
+            commands::watch_global_shortcut_readiness,
+            commands::get_status_summary,
+            commands::get_os_upgrade,
+            commands::open_onboarding,
+            commands::dismiss_reminder,
+            commands::reset_reminders,
+            commands::check_tcc_service,
+            commands::open_settings_for_service,
+            commands::open_settings_and_wait,
+            commands::watch_permission,
+            commands::subscribe_permission_updates,
+            commands::unwatch,
+            commands::cancel,
+            commands::ensure_permission,
+            commands::check_permission
         ])
+        .register_uri_scheme_protocol(onboarding::SCHEME, onboarding::protocol)
+        .on_event(file_drop::on_event)
+        .setup(|app_handle, api| {
+            let config = api.config().clone().unwrap_or_default();
+
+            config.validate()?;
+
+            let reminders = config.reminders.clone();
+            #[cfg(unix)]
+            let ipc = config.ipc.clone();
+
+            app_handle.manage(PermissionsManager::new(config));
+            app_handle.manage(Reminders::load(app_handle));
+            app_handle.manage(OsUpgrade::detect(app_handle));
+
+            #[cfg(target_os = "macos")]
+            if app_handle
+                .state::<PermissionsManager>()
+                .config()
+                .permissions
+                .contains(&PermissionKind::Location)
+            {
+                location::observe_authorization(app_handle)?;
+            }
+
+            // Without full disk access the databases cannot be observed, polling picks up the changes.
+            #[cfg(all(target_os = "macos", not(feature = "app-store")))]
+            if let Ok(home_dir) = app_handle.path().home_dir() {
+                if commands::check_full_disk_access_permission_blocking(app_handle) {
+                    tcc::observe_databases(app_handle, &home_dir)?;
+                }
+            }
+
+            PermissionsManager::spawn_poller(app_handle.clone());
+            OsUpgrade::spawn_verification(app_handle.clone());
+
+            if let Some(reminders) = reminders {
+                Reminders::spawn_scheduler(app_handle.clone(), reminders);
+            }
+
+            #[cfg(unix)]
+            if let Some(ipc) = ipc {
+                ipc::listen(app_handle, &ipc)?;
+            }
+
+            Ok(())
+        })
         .build()
 }
+
+/// Create a [`tauri_specta::Builder`] describing the plugin's commands and types.
+///
+/// Use it to export TypeScript bindings that stay in sync with the Rust side.
+///
+/// # Example
+/// ```
+/// let builder = tauri_plugin_macos_permissions::specta_builder::<tauri::Wry>();
+///
+/// builder
+///     .export(specta_typescript::Typescript::default(), "../src/bindings.ts")
+///     .expect("failed to export typescript bindings");
+/// ```
+#[cfg(feature = "specta")]
+pub fn specta_builder<R: Runtime>() -> tauri_specta::Builder<R> {
+    tauri_specta::Builder::<R>::new()
+        .plugin_name("macos-permissions")
+        .commands(tauri_specta::collect_commands![
+            commands::check_accessibility_permission,
+            commands::request_accessibility_permission::<R>,
+            commands::open_accessibility_settings,
+            commands::check_full_disk_access_permission::<R>,
+            commands::request_full_disk_access_permission::<R>,
+            commands::open_full_disk_access_settings,
+            commands::check_screen_recording_permission,
+            commands::request_screen_recording_permission::<R>,
+            commands::open_screen_recording_settings,
+            commands::check_microphone_permission,
+            commands::request_microphone_permission::<R>,
+            commands::open_microphone_settings,
+            commands::check_camera_permission,
+            commands::request_camera_permission::<R>,
+            commands::open_camera_settings,
+            commands::check_input_monitoring_permission,
+            commands::request_input_monitoring_permission::<R>,
+            commands::open_input_monitoring_settings,
+            commands::check_location_permission,
+            commands::request_location_permission::<R>,
+            commands::open_location_settings,
+            commands::get_current_position::<R>,
+            commands::check_notification_permission,
+            commands::request_notification_permission::<R>,
+            commands::open_notification_settings,
+            commands::list_capture_devices::<R>,
+            commands::verify_microphone_access,
+            commands::verify_screen_recording_access,
+            commands::verify_accessibility_access,
+            commands::check_event_tap_readiness,
+            commands::check_profile::<R>,
+            commands::has_been_requested::<R>,
+            commands::get_prompt_history::<R>,
+            commands::generate_pppc_profile::<R>,
+            commands::check_app_location,
+            commands::get_app_identity,
+            commands::find_app_copies,
+            commands::get_responsible_process,
+            commands::check_remote_session,
+            commands::is_virtual_machine,
+            commands::is_sip_enabled,
+            commands::get_plugin_info,
+            commands::get_permission_stats::<R>,
+            commands::request_path_access::<R>,
+            commands::check_global_shortcut_readiness,
+            commands::watch_global_shortcut_readiness::<R>,
+            commands::get_status_summary::<R>,
+            commands::get_os_upgrade::<R>,
+            commands::open_onboarding::<R>,
+            commands::dismiss_reminder::<R>,
+            commands::reset_reminders::<R>,
+            commands::check_tcc_service,
+            commands::open_settings_for_service,
+            commands::open_settings_and_wait::<R>,
+            commands::watch_permission::<R>,
+            commands::subscribe_permission_updates::<R>,
+            commands::unwatch::<R>,
+            commands::cancel::<R>,
+            commands::ensure_permission::<R>,
+            commands::check_permission::<R>
+        ])
+        .typ::<PermissionChanged>()
+        .typ::<PermissionsChanged>()
+        .typ::<OnboardingFinished>()
+        .typ::<Reminder>()
+        .typ::<OsUpgraded>()
+        .typ::<GrantCompleted>()
+        .typ::<FileDropResolved>()
+}
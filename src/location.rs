@@ -0,0 +1,240 @@
+use serde::Serialize;
+
+/// A position reported by CoreLocation, shaped like the web `GeolocationCoordinates`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct Position {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Horizontal accuracy in meters.
+    pub accuracy: f64,
+    pub altitude: Option<f64>,
+    /// Vertical accuracy in meters.
+    pub altitude_accuracy: Option<f64>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: f64,
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) use macos::*;
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::{sync::OnceLock, time::Duration};
+
+    use objc2::{
+        class, define_class,
+        encode::{Encode, Encoding},
+        msg_send,
+        rc::Retained,
+        runtime::{AnyObject, Bool, NSObject, NSObjectProtocol},
+        AllocAnyThread,
+    };
+    use tauri::{AppHandle, Manager, Runtime};
+
+    use super::Position;
+    use crate::{
+        error::Error, macos::catch_exception, manager::PermissionsManager, models::PermissionKind,
+        prompt::prompting,
+    };
+
+    #[link(name = "CoreLocation", kind = "framework")]
+    extern "C" {}
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CLLocationCoordinate2D {
+        latitude: f64,
+        longitude: f64,
+    }
+
+    unsafe impl Encode for CLLocationCoordinate2D {
+        const ENCODING: Encoding =
+            Encoding::Struct("CLLocationCoordinate2D", &[f64::ENCODING, f64::ENCODING]);
+    }
+
+    /// Called by [`LocationDelegate`] whenever the authorization changes, set by [`observe_authorization`].
+    static AUTHORIZATION_CHANGED: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
+
+    define_class!(
+        // SAFETY: NSObject has no subclassing requirements and `LocationDelegate` does not implement `Drop`.
+        #[unsafe(super(NSObject))]
+        #[name = "TauriPluginMacosPermissionsLocationDelegate"]
+        struct LocationDelegate;
+
+        unsafe impl NSObjectProtocol for LocationDelegate {}
+
+        impl LocationDelegate {
+            #[unsafe(method(locationManagerDidChangeAuthorization:))]
+            fn did_change_authorization(&self, _manager: &AnyObject) {
+                if let Some(callback) = AUTHORIZATION_CHANGED.get() {
+                    callback();
+                }
+            }
+
+            // Called instead of the method above before macOS 11.
+            #[unsafe(method(locationManager:didChangeAuthorizationStatus:))]
+            fn did_change_authorization_status(&self, manager: &AnyObject, _status: i32) {
+                self.did_change_authorization(manager);
+            }
+        }
+    );
+
+    thread_local! {
+        // CLLocationManager must be created and used on a thread with a run loop, the main thread here.
+        static LOCATION_MANAGER: Retained<AnyObject> =
+            unsafe { msg_send![class!(CLLocationManager), new] };
+
+        // The manager only keeps a weak reference to its delegate.
+        static LOCATION_DELEGATE: Retained<LocationDelegate> =
+            unsafe { msg_send![LocationDelegate::alloc(), init] };
+    }
+
+    /// Refresh the location status as soon as CoreLocation reports a change, instead of waiting for the next poll.
+    pub fn observe_authorization<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+        let handle = app_handle.clone();
+
+        let _ = AUTHORIZATION_CHANGED.set(Box::new(move || {
+            let app_handle = handle.clone();
+
+            // Delegate methods run on the main thread, `locationServicesEnabled` must not block it.
+            tauri::async_runtime::spawn(async move {
+                app_handle
+                    .state::<PermissionsManager>()
+                    .push(&app_handle, PermissionKind::Location);
+            });
+        }));
+
+        // Not waited for, this runs during setup on the main thread itself.
+        app_handle
+            .run_on_main_thread(|| {
+                LOCATION_MANAGER.with(|manager| {
+                    LOCATION_DELEGATE.with(|delegate| {
+                        // Without the delegate the status is still picked up by polling.
+                        let _ = catch_exception(|| unsafe {
+                            let _: () = msg_send![&**manager, setDelegate: &**delegate];
+                        });
+                    })
+                })
+            })
+            .map_err(|error| error.to_string())
+    }
+
+    /// Returns the raw `CLAuthorizationStatus`.
+    pub fn authorization_status() -> i32 {
+        // `kCLAuthorizationStatusDenied` if CoreLocation raised.
+        catch_exception(|| unsafe { msg_send![class!(CLLocationManager), authorizationStatus] })
+            .unwrap_or(2)
+    }
+
+    /// Returns `true` if location services are enabled system-wide.
+    pub fn location_services_enabled() -> bool {
+        catch_exception(|| {
+            let enabled: Bool =
+                unsafe { msg_send![class!(CLLocationManager), locationServicesEnabled] };
+
+            enabled.as_bool()
+        })
+        .unwrap_or(false)
+    }
+
+    /// Show the location prompt, if the user has not been asked yet.
+    pub fn request_authorization<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+        run_on_main_thread(app_handle, || {
+            prompting(|| {
+                LOCATION_MANAGER.with(|manager| {
+                    catch_exception(|| unsafe {
+                        let _: () = msg_send![&**manager, requestWhenInUseAuthorization];
+                    })
+                })
+            })
+        })?
+    }
+
+    /// Wait up to `timeout` for CoreLocation to report a position.
+    pub async fn current_position<R: Runtime>(
+        app_handle: &AppHandle<R>,
+        timeout: Duration,
+    ) -> Result<Position, Error> {
+        let interval = Duration::from_millis(100);
+        let mut elapsed = Duration::ZERO;
+
+        run_on_main_thread(app_handle, || {
+            LOCATION_MANAGER.with(|manager| {
+                catch_exception(|| unsafe {
+                    let _: () = msg_send![&**manager, startUpdatingLocation];
+                })
+            })
+        })??;
+
+        let position = loop {
+            if let Some(position) = run_on_main_thread(app_handle, last_position)? {
+                break Ok(position);
+            }
+
+            if elapsed >= timeout {
+                break Err(Error::TimedOut(PermissionKind::Location));
+            }
+
+            tokio::time::sleep(interval).await;
+
+            elapsed += interval;
+        };
+
+        run_on_main_thread(app_handle, || {
+            LOCATION_MANAGER.with(|manager| {
+                catch_exception(|| unsafe {
+                    let _: () = msg_send![&**manager, stopUpdatingLocation];
+                })
+            })
+        })??;
+
+        position
+    }
+
+    fn last_position() -> Option<Position> {
+        catch_exception(read_position).ok().flatten()
+    }
+
+    fn read_position() -> Option<Position> {
+        LOCATION_MANAGER.with(|manager| unsafe {
+            let location: Option<Retained<AnyObject>> = msg_send![&**manager, location];
+            let location = location?;
+
+            let coordinate: CLLocationCoordinate2D = msg_send![&*location, coordinate];
+            let accuracy: f64 = msg_send![&*location, horizontalAccuracy];
+            let altitude: f64 = msg_send![&*location, altitude];
+            let altitude_accuracy: f64 = msg_send![&*location, verticalAccuracy];
+            let date: Retained<AnyObject> = msg_send![&*location, timestamp];
+            let timestamp: f64 = msg_send![&*date, timeIntervalSince1970];
+
+            // A negative accuracy means the value is invalid.
+            let has_altitude = altitude_accuracy >= 0.0;
+
+            Some(Position {
+                latitude: coordinate.latitude,
+                longitude: coordinate.longitude,
+                accuracy,
+                altitude: has_altitude.then_some(altitude),
+                altitude_accuracy: has_altitude.then_some(altitude_accuracy),
+                timestamp: timestamp * 1000.0,
+            })
+        })
+    }
+
+    fn run_on_main_thread<R: Runtime, T: Send + 'static>(
+        app_handle: &AppHandle<R>,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<T, String> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        app_handle
+            .run_on_main_thread(move || {
+                let _ = sender.send(f());
+            })
+            .map_err(|error| error.to_string())?;
+
+        receiver.recv().map_err(|error| error.to_string())
+    }
+}
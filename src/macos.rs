@@ -0,0 +1,1044 @@
+use std::{
+    ffi::{c_char, c_void, CString},
+    fs::read_dir,
+    ops::Deref,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    ptr::null_mut,
+    sync::OnceLock,
+    time::Duration,
+};
+
+#[cfg(not(feature = "app-store"))]
+use std::process::Command;
+
+use block2::RcBlock;
+use objc2::{
+    class, msg_send,
+    rc::Retained,
+    runtime::{AnyObject, Bool},
+    Message,
+};
+use objc2_foundation::{NSArray, NSNumber, NSString};
+
+use crate::{
+    error::Error,
+    models::{CaptureDevice, PermissionKind, RemoteSessionKind, RequestOutcome},
+    prompt::prompting,
+};
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    static kAXTrustedCheckOptionPrompt: *const c_void;
+
+    pub fn AXIsProcessTrustedWithOptions(options: *const c_void) -> bool;
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    pub fn CGPreflightScreenCaptureAccess() -> bool;
+    pub fn CGRequestScreenCaptureAccess() -> bool;
+    pub fn CGSessionCopyCurrentDictionary() -> *const c_void;
+    pub fn CGWindowListCopyWindowInfo(option: u32, relative_to_window: u32) -> *const c_void;
+    pub fn CGEventTapCreate(
+        tap: u32,
+        place: u32,
+        options: u32,
+        events_of_interest: u64,
+        callback: CGEventTapCallBack,
+        user_info: *mut c_void,
+    ) -> *const c_void;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    pub fn CFRelease(cf: *const c_void);
+    pub fn CFMachPortInvalidate(port: *const c_void);
+}
+
+pub type CGEventTapCallBack = extern "C" fn(
+    proxy: *const c_void,
+    event_type: u32,
+    event: *const c_void,
+    user_info: *mut c_void,
+) -> *const c_void;
+
+extern "C" {
+    fn getxattr(
+        path: *const c_char,
+        name: *const c_char,
+        value: *mut c_void,
+        size: usize,
+        position: u32,
+        options: i32,
+    ) -> isize;
+    fn proc_pidpath(pid: i32, buffer: *mut c_void, buffer_size: u32) -> i32;
+    fn sysctlbyname(
+        name: *const c_char,
+        value: *mut c_void,
+        size: *mut usize,
+        new_value: *const c_void,
+        new_size: usize,
+    ) -> i32;
+}
+
+// Private, exported by libquarantine since macOS 10.14 and used by `launchctl procinfo`.
+#[cfg(not(feature = "app-store"))]
+extern "C" {
+    fn responsibility_get_pid_responsible_for_pid(pid: i32) -> i32;
+}
+
+// Undocumented or denied to other processes by the sandbox.
+#[cfg(not(feature = "app-store"))]
+extern "C" {
+    fn csr_get_active_config(config: *mut u32) -> i32;
+    fn proc_listallpids(buffer: *mut c_void, buffer_size: i32) -> i32;
+    fn proc_name(pid: i32, buffer: *mut c_void, buffer_size: u32) -> i32;
+}
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    pub fn IOHIDCheckAccess(request: u32) -> u32;
+    pub fn IOHIDRequestAccess(request: u32) -> bool;
+}
+
+/// `AVMediaTypeAudio`.
+pub const AV_MEDIA_TYPE_AUDIO: &str = "soun";
+
+/// `AVMediaTypeVideo`.
+pub const AV_MEDIA_TYPE_VIDEO: &str = "vide";
+
+/// `AVAuthorizationStatusAuthorized`.
+pub const AV_AUTHORIZATION_STATUS_AUTHORIZED: i32 = 3;
+
+/// `kIOHIDRequestTypeListenEvent`.
+pub const IOHID_REQUEST_TYPE_LISTEN_EVENT: u32 = 1;
+
+/// `kCGWindowListOptionOnScreenOnly`.
+pub const CG_WINDOW_LIST_OPTION_ON_SCREEN_ONLY: u32 = 1;
+
+/// `kCGSessionEventTap`.
+pub const CG_SESSION_EVENT_TAP: u32 = 1;
+
+/// `kCGHeadInsertEventTap`.
+pub const CG_HEAD_INSERT_EVENT_TAP: u32 = 0;
+
+/// `kCGEventTapOptionListenOnly`.
+pub const CG_EVENT_TAP_OPTION_LISTEN_ONLY: u32 = 1;
+
+/// `CGEventMaskBit(kCGEventKeyDown)`.
+pub const CG_EVENT_MASK_KEY_DOWN: u64 = 1 << 10;
+
+/// The process presenting the screen recording and accessibility prompts.
+pub const TCC_PROMPT_PROCESS: &str = "universalAccessAuthWarn";
+
+/// The bundle identifier of System Settings, and of System Preferences before macOS 13.
+pub const SYSTEM_SETTINGS_BUNDLE_ID: &str = "com.apple.systempreferences";
+
+/// Directories only readable with full disk access, relative to the home directory.
+///
+/// Containers of other apps are no probes, reading them prompts for app data access since macOS 14.
+///
+/// Reference: https://github.com/inket/FullDiskAccess/blob/846e04ea2b84fce843f47d7e7f3421189221829c/Sources/FullDiskAccess/FullDiskAccess.swift#L46
+pub const FULL_DISK_ACCESS_PROBES: [&str; 2] = [
+    "Library/Safari",
+    "Library/Application Support/com.apple.TCC",
+];
+
+/// An Objective-C object created once and shared between threads, see [`cached_string`].
+///
+/// Only for objects that are immutable or documented as thread safe, like strings and singletons.
+pub struct Shared<T: ?Sized + Message>(Retained<T>);
+
+unsafe impl<T: ?Sized + Message> Send for Shared<T> {}
+unsafe impl<T: ?Sized + Message> Sync for Shared<T> {}
+
+impl<T: ?Sized + Message> Shared<T> {
+    /// Share `object` between threads.
+    ///
+    /// # Safety
+    ///
+    /// `object` must be immutable or thread safe.
+    pub unsafe fn new(object: Retained<T>) -> Self {
+        Shared(object)
+    }
+}
+
+impl<T: ?Sized + Message> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Returns the string stored in `cache`, creating it from `value` the first time.
+///
+/// High-frequency checks would otherwise allocate the same strings on every call.
+pub fn cached_string<'a>(cache: &'a OnceLock<Shared<NSString>>, value: &str) -> &'a NSString {
+    // Strings are immutable.
+    cache.get_or_init(|| unsafe { Shared::new(NSString::from_str(value)) })
+}
+
+/// Returns the `AVMediaType` string of `media_type`, created once for the known ones.
+fn media_type_string(media_type: &str) -> Retained<NSString> {
+    static AUDIO: OnceLock<Shared<NSString>> = OnceLock::new();
+    static VIDEO: OnceLock<Shared<NSString>> = OnceLock::new();
+
+    let cache = match media_type {
+        AV_MEDIA_TYPE_AUDIO => &AUDIO,
+        AV_MEDIA_TYPE_VIDEO => &VIDEO,
+        _ => return NSString::from_str(media_type),
+    };
+
+    cached_string(cache, media_type).retain()
+}
+
+/// Run `f`, turning an Objective-C exception raised inside it into an error instead of aborting the process.
+pub fn catch_exception<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    objc2::exception::catch(AssertUnwindSafe(f)).map_err(|exception| match exception {
+        Some(exception) => format!("Objective-C exception: {exception:?}"),
+        None => "Objective-C exception".to_string(),
+    })
+}
+
+/// Returns `true` if the process is trusted for accessibility, showing the system prompt if `prompt` is set.
+pub fn is_process_trusted(prompt: bool) -> bool {
+    let trusted = || {
+        let prompt = NSNumber::new_bool(prompt);
+
+        catch_exception(|| unsafe {
+            let options: Retained<AnyObject> = msg_send![
+                class!(NSDictionary),
+                dictionaryWithObject: &*prompt,
+                forKey: kAXTrustedCheckOptionPrompt as *mut AnyObject
+            ];
+
+            AXIsProcessTrustedWithOptions(Retained::as_ptr(&options).cast())
+        })
+        .unwrap_or(false)
+    };
+
+    if prompt {
+        prompting(trusted)
+    } else {
+        trusted()
+    }
+}
+
+/// Open a System Settings deep link, e.g. `x-apple.systempreferences:com.apple.preference.notifications`.
+#[cfg(not(feature = "app-store"))]
+pub fn open_system_settings(url: &str) -> Result<(), String> {
+    prompting(|| Command::new("open").arg(url).output()).map_err(|error| error.to_string())?;
+
+    Ok(())
+}
+
+/// Open a System Settings deep link through `NSWorkspace`, sandboxed apps cannot spawn `open`.
+#[cfg(feature = "app-store")]
+pub fn open_system_settings(url: &str) -> Result<(), String> {
+    let link = NSString::from_str(url);
+
+    let opened = prompting(|| {
+        catch_exception(|| unsafe {
+            let link: Option<Retained<AnyObject>> = msg_send![class!(NSURL), URLWithString: &*link];
+
+            let Some(link) = link else {
+                return false;
+            };
+
+            let workspace: Retained<AnyObject> = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let opened: Bool = msg_send![&*workspace, openURL: &*link];
+
+            opened.as_bool()
+        })
+    })?;
+
+    if !opened {
+        return Err(format!("Failed to open {url}"));
+    }
+
+    Ok(())
+}
+
+/// Open the given pane of Privacy & Security in System Settings, e.g. `Privacy_Accessibility`.
+pub fn open_privacy_settings(anchor: &str) -> Result<(), String> {
+    open_system_settings(&format!(
+        "x-apple.systempreferences:com.apple.preference.security?{anchor}"
+    ))
+}
+
+/// Open the System Settings pane where `kind` is granted.
+pub fn open_permission_settings(kind: PermissionKind) -> Result<(), Error> {
+    ensure_gui_session()?;
+
+    let anchor = match kind {
+        PermissionKind::Accessibility => "Privacy_Accessibility",
+        PermissionKind::FullDiskAccess => "Privacy_AllFiles",
+        PermissionKind::ScreenRecording => "Privacy_ScreenCapture",
+        PermissionKind::Microphone => "Privacy_Microphone",
+        PermissionKind::Camera => "Privacy_Camera",
+        PermissionKind::InputMonitoring => "Privacy_ListenEvent",
+        PermissionKind::Location => "Privacy_LocationServices",
+        // Notifications have their own pane outside of Privacy & Security.
+        PermissionKind::Notifications => {
+            return open_system_settings(
+                "x-apple.systempreferences:com.apple.preference.notifications",
+            )
+            .map_err(Error::from)
+        }
+    };
+
+    open_privacy_settings(anchor).map_err(Error::from)
+}
+
+/// Returns the raw `AVAuthorizationStatus` for the given media type.
+pub fn authorization_status(media_type: &str) -> i32 {
+    let av_media_type = media_type_string(media_type);
+
+    catch_exception(|| unsafe {
+        msg_send![
+            class!(AVCaptureDevice),
+            authorizationStatusForMediaType: &*av_media_type
+        ]
+    })
+    // `AVAuthorizationStatusDenied`, an error must never read as granted.
+    .unwrap_or(2)
+}
+
+/// Returns `true` if a capture device for the given media type is connected, this does not require permission.
+pub fn has_capture_device(media_type: &str) -> bool {
+    let av_media_type = media_type_string(media_type);
+
+    catch_exception(|| {
+        let device: Option<Retained<AnyObject>> = unsafe {
+            msg_send![
+                class!(AVCaptureDevice),
+                defaultDeviceWithMediaType: &*av_media_type
+            ]
+        };
+
+        device.is_some()
+    })
+    .unwrap_or(false)
+}
+
+/// Returns the capture devices for the given media type.
+pub fn capture_devices(media_type: &str) -> Result<Vec<CaptureDevice>, String> {
+    let av_media_type = media_type_string(media_type);
+
+    catch_exception(|| {
+        let devices: Retained<NSArray<AnyObject>> = unsafe {
+            msg_send![
+                class!(AVCaptureDevice),
+                devicesWithMediaType: &*av_media_type
+            ]
+        };
+
+        devices
+            .iter()
+            .map(|device| unsafe {
+                let id: Retained<NSString> = msg_send![&*device, uniqueID];
+                let name: Retained<NSString> = msg_send![&*device, localizedName];
+                let model_id: Retained<NSString> = msg_send![&*device, modelID];
+
+                CaptureDevice {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    model_id: model_id.to_string(),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Returns the path of the running app bundle, or of the directory of the executable outside of a bundle.
+pub fn bundle_path() -> String {
+    catch_exception(|| unsafe {
+        let bundle: Retained<AnyObject> = msg_send![class!(NSBundle), mainBundle];
+        let path: Retained<NSString> = msg_send![&*bundle, bundlePath];
+
+        path.to_string()
+    })
+    .unwrap_or_default()
+}
+
+/// Returns the bundle identifier of the running app, `None` outside of a bundle.
+pub fn bundle_identifier() -> Option<String> {
+    catch_exception(|| unsafe {
+        let bundle: Retained<AnyObject> = msg_send![class!(NSBundle), mainBundle];
+        let identifier: Option<Retained<NSString>> = msg_send![&*bundle, bundleIdentifier];
+
+        identifier.map(|identifier| identifier.to_string())
+    })
+    .ok()
+    .flatten()
+}
+
+/// Returns the signing identity and team identifier of the running app, as printed by `codesign -dv`.
+pub fn code_signature() -> (Option<String>, Option<String>) {
+    code_signature_of(&bundle_path())
+}
+
+/// Returns the signing identity and team identifier of the app at `path`, see [`code_signature`].
+#[cfg(not(feature = "app-store"))]
+pub fn code_signature_of(path: &str) -> (Option<String>, Option<String>) {
+    let Ok(output) = Command::new("codesign")
+        .args(["-d", "-v", "-v"])
+        .arg(path)
+        .output()
+    else {
+        return (None, None);
+    };
+
+    // The details are printed to stderr.
+    let details = String::from_utf8_lossy(&output.stderr);
+    let value = |key: &str| {
+        details
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(str::to_string)
+    };
+
+    let identity = value("Authority=")
+        .or_else(|| value("Signature=").filter(|signature| signature == "adhoc"));
+    let team_id = value("TeamIdentifier=").filter(|team_id| team_id != "not set");
+
+    (identity, team_id)
+}
+
+/// `codesign` cannot be spawned from the sandbox, App Store builds are always signed by Apple.
+#[cfg(feature = "app-store")]
+pub fn code_signature_of(_path: &str) -> (Option<String>, Option<String>) {
+    (None, None)
+}
+
+/// Returns the paths of every copy of the app with `bundle_id` known to LaunchServices, empty before macOS 12.
+pub fn app_copies(bundle_id: &str) -> Vec<String> {
+    let bundle_id = NSString::from_str(bundle_id);
+
+    catch_exception(|| unsafe {
+        let workspace: Retained<AnyObject> = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let urls: Retained<NSArray<AnyObject>> =
+            msg_send![&*workspace, URLsForApplicationsWithBundleIdentifier: &*bundle_id];
+
+        urls.iter()
+            .filter_map(|url| {
+                let path: Option<Retained<NSString>> = msg_send![&*url, path];
+
+                path.map(|path| path.to_string())
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Returns the `CFBundleShortVersionString` of the app bundle at `path`.
+pub fn bundle_version(path: &str) -> Option<String> {
+    static KEY: OnceLock<Shared<NSString>> = OnceLock::new();
+
+    let path = NSString::from_str(path);
+
+    catch_exception(|| unsafe {
+        let bundle: Option<Retained<AnyObject>> =
+            msg_send![class!(NSBundle), bundleWithPath: &*path];
+        // A string in every valid Info.plist.
+        let version: Option<Retained<NSString>> = msg_send![
+            &*bundle?,
+            objectForInfoDictionaryKey: cached_string(&KEY, "CFBundleShortVersionString")
+        ];
+
+        version.map(|version| version.to_string())
+    })
+    .ok()
+    .flatten()
+}
+
+/// Returns the bundle identifier of the app bundle at `path`.
+pub fn bundle_identifier_of(path: &str) -> Option<String> {
+    let path = NSString::from_str(path);
+
+    catch_exception(|| unsafe {
+        let bundle: Option<Retained<AnyObject>> =
+            msg_send![class!(NSBundle), bundleWithPath: &*path];
+        let identifier: Option<Retained<NSString>> = msg_send![&*bundle?, bundleIdentifier];
+
+        identifier.map(|identifier| identifier.to_string())
+    })
+    .ok()
+    .flatten()
+}
+
+/// Returns the executable path of the process `pid`.
+pub fn process_path(pid: i32) -> Option<String> {
+    // `PROC_PIDPATHINFO_MAXSIZE`.
+    let mut buffer = [0u8; 4096];
+    let len = unsafe { proc_pidpath(pid, buffer.as_mut_ptr().cast(), buffer.len() as u32) };
+
+    (len > 0).then(|| String::from_utf8_lossy(&buffer[..len as usize]).into_owned())
+}
+
+/// Returns the pid of the process TCC attributes the requests of the running app to.
+///
+/// That is the app itself when launched by launchd, e.g. from Finder, and the terminal or IDE that
+/// spawned it otherwise.
+#[cfg(not(feature = "app-store"))]
+pub fn responsible_pid() -> Option<i32> {
+    let pid = unsafe { responsibility_get_pid_responsible_for_pid(std::process::id() as i32) };
+
+    (pid > 0).then_some(pid)
+}
+
+/// App Review rejects private APIs.
+#[cfg(feature = "app-store")]
+pub fn responsible_pid() -> Option<i32> {
+    None
+}
+
+/// Returns `true` unless any protection of System Integrity Protection is turned off, as by `csrutil disable`.
+///
+/// Returns `None` if the configuration cannot be read, as before OS X 10.11 without SIP support.
+#[cfg(not(feature = "app-store"))]
+pub fn is_sip_enabled() -> Option<bool> {
+    let mut config = 0;
+
+    if unsafe { csr_get_active_config(&mut config) } != 0 {
+        return None;
+    }
+
+    Some(config == 0)
+}
+
+/// App Review rejects private APIs, SIP cannot be checked.
+#[cfg(feature = "app-store")]
+pub fn is_sip_enabled() -> Option<bool> {
+    None
+}
+
+/// Returns the value of the sysctl `name`, `None` if it does not exist.
+fn sysctl(name: &str) -> Option<Vec<u8>> {
+    let name = CString::new(name).ok()?;
+    let mut size = 0;
+
+    unsafe {
+        if sysctlbyname(name.as_ptr(), null_mut(), &mut size, null_mut(), 0) != 0 {
+            return None;
+        }
+
+        let mut value = vec![0u8; size];
+
+        if sysctlbyname(
+            name.as_ptr(),
+            value.as_mut_ptr().cast(),
+            &mut size,
+            null_mut(),
+            0,
+        ) != 0
+        {
+            return None;
+        }
+
+        value.truncate(size);
+
+        Some(value)
+    }
+}
+
+/// Returns the running macOS version, e.g. `15.1`.
+pub fn os_version() -> Option<String> {
+    let value = sysctl("kern.osproductversion")?;
+
+    // The value is NUL terminated.
+    let value = value.split(|&byte| byte == 0).next()?;
+
+    String::from_utf8(value.to_vec()).ok()
+}
+
+/// Returns `true` if the Mac is a virtual machine, such as on VM-based CI runners.
+///
+/// `kern.hv_vmm_present` is set by every hypervisor since macOS 11, older ones are told by the
+/// `hw.model` of VMware, Parallels and Apple Virtualization.
+pub fn is_virtual_machine() -> bool {
+    let hypervisor = sysctl("kern.hv_vmm_present")
+        .and_then(|value| value.first().copied())
+        .is_some_and(|value| value != 0);
+
+    hypervisor
+        || sysctl("hw.model").is_some_and(|model| {
+            let model = String::from_utf8_lossy(&model);
+
+            ["VMware", "Parallels", "VirtualMac"]
+                .iter()
+                .any(|vendor| model.contains(vendor))
+        })
+}
+
+/// Returns `true` if a process named `name` is running.
+#[cfg(not(feature = "app-store"))]
+pub fn is_process_running(name: &str) -> bool {
+    let count = unsafe { proc_listallpids(null_mut(), 0) };
+
+    if count <= 0 {
+        return false;
+    }
+
+    // Leave room for processes started in between.
+    let mut pids = vec![0i32; count as usize + 32];
+    let size = (pids.len() * std::mem::size_of::<i32>()) as i32;
+    let count = unsafe { proc_listallpids(pids.as_mut_ptr().cast(), size) };
+
+    pids.truncate(count.max(0) as usize);
+
+    pids.into_iter().any(|pid| {
+        let mut buffer = [0u8; 64];
+        let len = unsafe { proc_name(pid, buffer.as_mut_ptr().cast(), buffer.len() as u32) };
+
+        len > 0 && &buffer[..len as usize] == name.as_bytes()
+    })
+}
+
+/// The sandbox hides the other processes, none is reported running.
+#[cfg(feature = "app-store")]
+pub fn is_process_running(_name: &str) -> bool {
+    false
+}
+
+/// Returns the remote session the user is connected through, prompts may not be presentable there.
+///
+/// `screensharingd` only runs while someone is connected, with `ARDAgent` also running the
+/// connection comes from Apple Remote Desktop.
+pub fn remote_session() -> Option<RemoteSessionKind> {
+    let ssh = ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+        .iter()
+        .any(|name| std::env::var_os(name).is_some());
+
+    if ssh {
+        return Some(RemoteSessionKind::Ssh);
+    }
+
+    if !is_process_running("screensharingd") {
+        return None;
+    }
+
+    Some(if is_process_running("ARDAgent") {
+        RemoteSessionKind::RemoteManagement
+    } else {
+        RemoteSessionKind::ScreenSharing
+    })
+}
+
+/// Returns `true` if the path carries the `com.apple.quarantine` attribute set on downloads.
+pub fn is_quarantined(path: &str) -> bool {
+    let (Ok(path), Ok(name)) = (CString::new(path), CString::new("com.apple.quarantine")) else {
+        return false;
+    };
+
+    unsafe { getxattr(path.as_ptr(), name.as_ptr(), null_mut(), 0, 0, 0) >= 0 }
+}
+
+/// Returns the designated code requirement of the running app, as printed by `codesign -dr -`.
+#[cfg(not(feature = "app-store"))]
+pub fn designated_requirement() -> Result<String, String> {
+    let output = Command::new("codesign")
+        .args(["-d", "-r", "-"])
+        .arg(bundle_path())
+        .output()
+        .map_err(|error| error.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("designated => "))
+        .map(str::to_string)
+        .ok_or_else(|| "The app has no designated requirement, is it signed?".to_string())
+}
+
+/// `codesign` cannot be spawned from the sandbox, PPPC profiles are for apps deployed with MDM anyway.
+#[cfg(feature = "app-store")]
+pub fn designated_requirement() -> Result<String, String> {
+    Err("The designated requirement is not available in App Store builds".to_string())
+}
+
+/// Returns the first full disk access probe that could be read.
+pub fn full_disk_access_probe(home_dir: &Path) -> Option<PathBuf> {
+    FULL_DISK_ACCESS_PROBES
+        .iter()
+        .map(|probe| home_dir.join(probe))
+        .find(|path| read_dir(path).is_ok())
+}
+
+/// Returns `true` if the app runs in the App Sandbox, which sets `APP_SANDBOX_CONTAINER_ID` for it.
+pub fn is_sandboxed() -> bool {
+    std::env::var_os("APP_SANDBOX_CONTAINER_ID").is_some()
+}
+
+/// Returns `true` if the process runs inside a window server session.
+///
+/// SSH sessions, CI runners and launchd daemons have no session, prompts and
+/// System Settings cannot be presented there.
+pub fn has_gui_session() -> bool {
+    unsafe {
+        let session = CGSessionCopyCurrentDictionary();
+
+        if session.is_null() {
+            return false;
+        }
+
+        CFRelease(session);
+
+        true
+    }
+}
+
+/// Returns `false` while the session is locked, switched away from, or the screen saver runs.
+///
+/// Prompts shown then are missed, and some of them can only be shown once.
+pub fn is_user_present() -> bool {
+    static LOCKED_KEY: OnceLock<Shared<NSString>> = OnceLock::new();
+    static ON_CONSOLE_KEY: OnceLock<Shared<NSString>> = OnceLock::new();
+
+    if is_process_running("ScreenSaverEngine") {
+        return false;
+    }
+
+    unsafe {
+        let session = CGSessionCopyCurrentDictionary();
+
+        // Without a session requests fail right away instead of waiting.
+        if session.is_null() {
+            return true;
+        }
+
+        // CFDictionary is toll-free bridged to NSDictionary.
+        let dictionary = session as *mut AnyObject;
+
+        let bool_for_key = |key: &NSString| {
+            let value: *mut AnyObject = msg_send![dictionary, objectForKey: key];
+
+            (!value.is_null()).then(|| msg_send![value, boolValue])
+        };
+
+        let present = catch_exception(|| {
+            let locked = bool_for_key(cached_string(&LOCKED_KEY, "CGSSessionScreenIsLocked"));
+            let on_console =
+                bool_for_key(cached_string(&ON_CONSOLE_KEY, "kCGSSessionOnConsoleKey"));
+
+            locked != Some(true) && on_console != Some(false)
+        })
+        .unwrap_or(true);
+
+        CFRelease(session);
+
+        present
+    }
+}
+
+/// Show a modal alert with `Continue` and `Not Now` buttons, returns whether the user continued.
+///
+/// Must be called on the main thread.
+pub fn run_priming_alert(title: &str, message: &str) -> Result<bool, String> {
+    // Reference: https://developer.apple.com/documentation/appkit/nsapplication/modalresponse/alertfirstbuttonreturn
+    const ALERT_FIRST_BUTTON_RETURN: isize = 1000;
+
+    prompting(|| {
+        catch_exception(|| unsafe {
+            let alert: Retained<AnyObject> = msg_send![class!(NSAlert), new];
+
+            let _: () = msg_send![&*alert, setMessageText: &*NSString::from_str(title)];
+            let _: () = msg_send![&*alert, setInformativeText: &*NSString::from_str(message)];
+            let _: *mut AnyObject =
+                msg_send![&*alert, addButtonWithTitle: &*NSString::from_str("Continue")];
+            let _: *mut AnyObject =
+                msg_send![&*alert, addButtonWithTitle: &*NSString::from_str("Not Now")];
+
+            let response: isize = msg_send![&*alert, runModal];
+
+            response == ALERT_FIRST_BUTTON_RETURN
+        })
+    })
+}
+
+/// Fails if there is no window server session to present a request in.
+pub fn ensure_gui_session() -> Result<(), Error> {
+    if has_gui_session() {
+        Ok(())
+    } else {
+        Err(Error::NoGuiSession)
+    }
+}
+
+/// An on-screen window, as reported by `CGWindowListCopyWindowInfo`.
+pub struct WindowInfo {
+    pub owner_name: Option<String>,
+    pub owner_pid: i32,
+    /// Only readable with screen recording permission, except for the app's own windows.
+    pub name: Option<String>,
+}
+
+/// Returns the on-screen windows of all apps.
+pub fn on_screen_windows() -> Vec<WindowInfo> {
+    static OWNER_NAME_KEY: OnceLock<Shared<NSString>> = OnceLock::new();
+    static OWNER_PID_KEY: OnceLock<Shared<NSString>> = OnceLock::new();
+    static NAME_KEY: OnceLock<Shared<NSString>> = OnceLock::new();
+
+    // Polled while waiting for prompts.
+    let owner_name_key = cached_string(&OWNER_NAME_KEY, "kCGWindowOwnerName");
+    let owner_pid_key = cached_string(&OWNER_PID_KEY, "kCGWindowOwnerPID");
+    let name_key = cached_string(&NAME_KEY, "kCGWindowName");
+
+    unsafe {
+        let windows = CGWindowListCopyWindowInfo(CG_WINDOW_LIST_OPTION_ON_SCREEN_ONLY, 0);
+
+        if windows.is_null() {
+            return Vec::new();
+        }
+
+        // CFArray and CFDictionary are toll-free bridged to NSArray and NSDictionary.
+        let array = windows as *mut AnyObject;
+
+        let string_for_key = |window: *mut AnyObject, key: &NSString| {
+            let value: *mut AnyObject = msg_send![window, objectForKey: key];
+
+            (!value.is_null()).then(|| (*(value as *const NSString)).to_string())
+        };
+
+        let infos = catch_exception(|| {
+            let count: usize = msg_send![array, count];
+
+            (0..count)
+                .map(|index| {
+                    let window: *mut AnyObject = msg_send![array, objectAtIndex: index];
+                    let owner_pid: *mut AnyObject =
+                        msg_send![window, objectForKey: &*owner_pid_key];
+
+                    WindowInfo {
+                        owner_name: string_for_key(window, owner_name_key),
+                        owner_pid: if owner_pid.is_null() {
+                            0
+                        } else {
+                            msg_send![owner_pid, intValue]
+                        },
+                        name: string_for_key(window, name_key),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+        // Released even if reading it raised, the array is owned by us.
+        CFRelease(windows);
+
+        infos
+    }
+}
+
+/// Returns `true` if a window owned by the process named `owner` is on screen.
+///
+/// Window owner names are readable without screen recording permission.
+pub fn has_window_owned_by(owner: &str) -> bool {
+    on_screen_windows()
+        .iter()
+        .any(|window| window.owner_name.as_deref() == Some(owner))
+}
+
+/// An `NSWorkspace` observer for an app terminating, removed when dropped.
+pub struct TerminationObserver {
+    center: Retained<AnyObject>,
+    observer: Retained<AnyObject>,
+    pub terminated: tokio::sync::mpsc::UnboundedReceiver<()>,
+}
+
+// The notification center is thread safe, the observer is only handed back to it.
+unsafe impl Send for TerminationObserver {}
+
+impl Drop for TerminationObserver {
+    fn drop(&mut self) {
+        let _ = catch_exception(|| unsafe {
+            let _: () = msg_send![&*self.center, removeObserver: &*self.observer];
+        });
+    }
+}
+
+/// Observe the app with `bundle_id` terminating, e.g. System Settings being closed.
+pub fn observe_termination(bundle_id: &'static str) -> Result<TerminationObserver, String> {
+    let (sender, terminated) = tokio::sync::mpsc::unbounded_channel();
+
+    let name = NSString::from_str("NSWorkspaceDidTerminateApplicationNotification");
+    let application_key = NSString::from_str("NSWorkspaceApplicationKey");
+
+    let block = RcBlock::new(move |notification: *mut AnyObject| {
+        // Called by AppKit, an exception must not unwind out of the block.
+        let terminated = catch_exception(|| unsafe {
+            let user_info: *mut AnyObject = msg_send![notification, userInfo];
+
+            if user_info.is_null() {
+                return false;
+            }
+
+            let application: *mut AnyObject = msg_send![user_info, objectForKey: &*application_key];
+
+            if application.is_null() {
+                return false;
+            }
+
+            let identifier: Option<Retained<NSString>> = msg_send![application, bundleIdentifier];
+
+            identifier.is_some_and(|identifier| identifier.to_string() == bundle_id)
+        });
+
+        if terminated == Ok(true) {
+            let _ = sender.send(());
+        }
+    });
+
+    catch_exception(|| unsafe {
+        let workspace: Retained<AnyObject> = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let center: Retained<AnyObject> = msg_send![&*workspace, notificationCenter];
+
+        // Without a queue the block runs on the posting thread, the main thread for workspace notifications.
+        let observer: Retained<AnyObject> = msg_send![
+            &*center,
+            addObserverForName: &*name,
+            object: null_mut::<AnyObject>(),
+            queue: null_mut::<AnyObject>(),
+            usingBlock: &*block
+        ];
+
+        TerminationObserver {
+            center,
+            observer,
+            terminated,
+        }
+    })
+}
+
+/// Wait up to two seconds for the TCC prompt to appear, it is presented asynchronously by another process.
+pub async fn wait_for_tcc_prompt() -> bool {
+    for _ in 0..8 {
+        if has_window_owned_by(TCC_PROMPT_PROCESS) {
+            return true;
+        }
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    false
+}
+
+/// Wait until the TCC prompt is closed or `granted` returns `true`, then return whether the permission is granted.
+pub async fn wait_for_tcc_response(granted: impl Fn() -> bool) -> bool {
+    while has_window_owned_by(TCC_PROMPT_PROCESS) {
+        if granted() {
+            return true;
+        }
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    granted()
+}
+
+/// Request access to `media_type`, resolving once the user responded to the prompt.
+pub async fn request_media_access(media_type: &str) -> Result<RequestOutcome, String> {
+    // Reference: https://developer.apple.com/documentation/avfoundation/avauthorizationstatus
+    match authorization_status(media_type) {
+        AV_AUTHORIZATION_STATUS_AUTHORIZED => return Ok(RequestOutcome::Granted),
+        1 => return Ok(RequestOutcome::PromptUnavailable),
+        2 => return Ok(RequestOutcome::Denied),
+        _ => {}
+    }
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    // The block and the retained string are not `Send`, they must be dropped before awaiting.
+    {
+        let av_media_type = media_type_string(media_type);
+        let completion_handler = RcBlock::new(move |granted: Bool| {
+            let _ = sender.send(granted.as_bool());
+        });
+
+        prompting(|| {
+            catch_exception(|| unsafe {
+                let _: () = msg_send![
+                    class!(AVCaptureDevice),
+                    requestAccessForMediaType: &*av_media_type,
+                    completionHandler: &*completion_handler
+                ];
+            })
+        })?;
+    }
+
+    Ok(match receiver.recv().await {
+        Some(true) => RequestOutcome::Granted,
+        _ => RequestOutcome::Denied,
+    })
+}
+
+extern "C" fn passthrough_event_tap(
+    _proxy: *const c_void,
+    _event_type: u32,
+    event: *const c_void,
+    _user_info: *mut c_void,
+) -> *const c_void {
+    event
+}
+
+/// Returns `true` if a listen-only key event tap can be created, the tap is invalidated right away.
+///
+/// Creating the tap prompts for input monitoring if the user was not asked yet, only call it once granted.
+pub fn can_create_event_tap() -> bool {
+    unsafe {
+        let tap = CGEventTapCreate(
+            CG_SESSION_EVENT_TAP,
+            CG_HEAD_INSERT_EVENT_TAP,
+            CG_EVENT_TAP_OPTION_LISTEN_ONLY,
+            CG_EVENT_MASK_KEY_DOWN,
+            passthrough_event_tap,
+            null_mut(),
+        );
+
+        if tap.is_null() {
+            return false;
+        }
+
+        CFMachPortInvalidate(tap);
+        CFRelease(tap);
+
+        true
+    }
+}
+
+extern "C" {
+    fn flock(fd: i32, operation: i32) -> i32;
+}
+
+/// Take an exclusive lock of the file at `path` unless another process holds it, released with the returned file.
+pub fn try_lock_file(path: &Path) -> std::io::Result<Option<std::fs::File>> {
+    use std::os::fd::AsRawFd;
+
+    // Reference: https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/flock.2.html
+    const LOCK_EX: i32 = 2;
+    const LOCK_NB: i32 = 4;
+
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+
+    if unsafe { flock(file.as_raw_fd(), LOCK_EX | LOCK_NB) } == 0 {
+        return Ok(Some(file));
+    }
+
+    let error = std::io::Error::last_os_error();
+
+    // `EWOULDBLOCK`, another process holds the lock.
+    if error.raw_os_error() == Some(35) {
+        return Ok(None);
+    }
+
+    Err(error)
+}
@@ -0,0 +1,423 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use futures_util::{stream, Stream};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, Runtime};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{
+    config::Config,
+    error::Error,
+    models::{
+        GrantCompleted, PermissionDetails, PermissionKind, PermissionStats, PermissionStatus,
+        PermissionStatusEntry, StatusChange, StatusSummary,
+    },
+    status,
+    watcher::Watchers,
+};
+#[cfg(target_os = "macos")]
+use crate::{coordinator::Coordinator, models::RequestOutcome};
+
+/// Emitted when a permission is granted after a request opened System Settings, with a [`GrantCompleted`] payload.
+pub const GRANT_COMPLETED_EVENT: &str = "macos-permissions://grant-completed";
+
+/// Shared permission state, kept in the managed state.
+///
+/// # Example
+/// ```
+/// use tauri::Manager;
+/// use tauri_plugin_macos_permissions::{PermissionKind, PermissionsManager};
+///
+/// let manager = app.state::<PermissionsManager>();
+/// let status = manager.status(app.handle(), PermissionKind::Camera);
+/// println!("Status: {:?}", status); // Granted
+/// ```
+pub struct PermissionsManager {
+    config: Config,
+    statuses: Mutex<HashMap<PermissionKind, PermissionStatus>>,
+    watchers: Watchers,
+    changes: broadcast::Sender<StatusChange>,
+    /// The number of streams returned by [`subscribe`](Self::subscribe) for each kind.
+    subscribed: Arc<Mutex<HashMap<PermissionKind, usize>>>,
+    awaiting_grant: Mutex<HashSet<PermissionKind>>,
+    pushes: broadcast::Sender<PermissionKind>,
+    stats: Stats,
+    #[cfg(target_os = "macos")]
+    coordinator: Coordinator,
+}
+
+/// The counters behind [`PermissionsManager::stats`].
+struct Stats {
+    started: Instant,
+    checks: AtomicU64,
+    prompts_shown: AtomicU64,
+    settings_opened: AtomicU64,
+    grants_observed: AtomicU64,
+}
+
+impl Default for PermissionsManager {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+impl PermissionsManager {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            statuses: Default::default(),
+            watchers: Default::default(),
+            changes: broadcast::channel(16).0,
+            subscribed: Default::default(),
+            awaiting_grant: Default::default(),
+            pushes: broadcast::channel(16).0,
+            stats: Stats {
+                started: Instant::now(),
+                checks: Default::default(),
+                prompts_shown: Default::default(),
+                settings_opened: Default::default(),
+                grants_observed: Default::default(),
+            },
+            #[cfg(target_os = "macos")]
+            coordinator: Default::default(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn watchers(&self) -> &Watchers {
+        &self.watchers
+    }
+
+    /// Emit `event` to the `event_targets` config, under the `event_prefix` config.
+    pub(crate) fn emit<R: Runtime, S: Serialize + Clone>(
+        &self,
+        app_handle: &AppHandle<R>,
+        event: &str,
+        payload: S,
+    ) {
+        self.emit_to(app_handle, &self.config.event_targets, event, payload);
+    }
+
+    /// Emit `event` to the windows and webviews labeled `targets`, or to every one of them when empty.
+    pub(crate) fn emit_to<R: Runtime, S: Serialize + Clone>(
+        &self,
+        app_handle: &AppHandle<R>,
+        targets: &[String],
+        event: &str,
+        payload: S,
+    ) {
+        let event = self.config.event_name(event);
+
+        if targets.is_empty() {
+            let _ = app_handle.emit(&event, payload);
+
+            return;
+        }
+
+        let _ = app_handle.emit_filter(&event, payload, |target| match target {
+            EventTarget::AnyLabel { label }
+            | EventTarget::Window { label }
+            | EventTarget::Webview { label }
+            | EventTarget::WebviewWindow { label } => targets.contains(label),
+            _ => false,
+        });
+    }
+
+    /// The last known status of `kind`, without querying the system.
+    pub fn cached_status(&self, kind: PermissionKind) -> Option<PermissionStatus> {
+        self.statuses.lock().unwrap().get(&kind).copied()
+    }
+
+    /// The cached status of `kind`, queried from the system the first time.
+    pub fn status<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        kind: PermissionKind,
+    ) -> PermissionStatus {
+        match self.cached_status(kind) {
+            Some(status) => status,
+            None => self.refresh(app_handle, kind),
+        }
+    }
+
+    /// Query the system for the status of `kind` and update the cache.
+    pub fn refresh<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        kind: PermissionKind,
+    ) -> PermissionStatus {
+        self.probe(app_handle, kind).0
+    }
+
+    /// Like [`refresh`](Self::refresh), also returning the raw values the status was derived from.
+    pub fn probe<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        kind: PermissionKind,
+    ) -> (PermissionStatus, PermissionDetails) {
+        let (status, details) = status::probe(app_handle, kind);
+
+        self.stats.checks.fetch_add(1, Ordering::Relaxed);
+
+        let previous = self.statuses.lock().unwrap().insert(kind, status);
+
+        if let Some(previous) = previous.filter(|previous| *previous != status) {
+            if status.is_granted() {
+                self.stats.grants_observed.fetch_add(1, Ordering::Relaxed);
+            }
+
+            let _ = self.changes.send(StatusChange {
+                kind,
+                status,
+                previous,
+            });
+        }
+
+        if status.is_granted() && self.awaiting_grant.lock().unwrap().remove(&kind) {
+            let refocused = self.config.refocus_on_grant && focus_main_window(app_handle);
+
+            self.emit(
+                app_handle,
+                GRANT_COMPLETED_EVENT,
+                GrantCompleted { kind, refocused },
+            );
+        }
+
+        (status, details)
+    }
+
+    /// Run the request of `kind` once for all the windows and instances of the app requesting it together.
+    ///
+    /// The callers coming in while it runs resolve with its outcome instead of prompting again.
+    #[cfg(target_os = "macos")]
+    pub(crate) async fn coordinate<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        kind: PermissionKind,
+        request: impl std::future::Future<Output = Result<RequestOutcome, Error>>,
+    ) -> Result<RequestOutcome, Error> {
+        let request = async {
+            // Cached before and refreshed after, so a grant in the prompt is observed right away.
+            self.status(app_handle, kind);
+
+            let outcome = request.await;
+
+            self.refresh(app_handle, kind);
+
+            outcome
+        };
+
+        self.coordinator.run(app_handle, kind, request).await
+    }
+
+    /// The counters of the current session, telling e.g. how often the permissions are polled.
+    ///
+    /// # Example
+    /// ```
+    /// use tauri::Manager;
+    /// use tauri_plugin_macos_permissions::PermissionsManager;
+    ///
+    /// let stats = app.state::<PermissionsManager>().stats();
+    /// println!("Checks: {}", stats.checks); // 12
+    /// ```
+    pub fn stats(&self) -> PermissionStats {
+        let stats = &self.stats;
+
+        PermissionStats {
+            checks: stats.checks.load(Ordering::Relaxed),
+            prompts_shown: stats.prompts_shown.load(Ordering::Relaxed),
+            settings_opened: stats.settings_opened.load(Ordering::Relaxed),
+            grants_observed: stats.grants_observed.load(Ordering::Relaxed),
+            elapsed: stats.started.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Count a system prompt shown by a request.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn prompt_shown(&self) {
+        self.stats.prompts_shown.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Open the System Settings pane of `kind` and count it, for the flows of the plugin.
+    pub(crate) fn open_settings(&self, kind: PermissionKind) -> Result<(), Error> {
+        kind.open_settings()?;
+
+        self.stats.settings_opened.fetch_add(1, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Open the Privacy & Security pane at `anchor` and count it, for the panes without a [`PermissionKind`].
+    #[cfg(target_os = "macos")]
+    pub(crate) fn open_privacy_settings(&self, anchor: &str) -> Result<(), Error> {
+        crate::macos::ensure_gui_session()?;
+        crate::macos::open_privacy_settings(anchor)?;
+
+        self.stats.settings_opened.fetch_add(1, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Poll `kind` until it is granted, then emit [`GRANT_COMPLETED_EVENT`].
+    ///
+    /// Request commands call it once they opened System Settings, call it after opening it yourself.
+    pub fn await_grant(&self, kind: PermissionKind) {
+        self.awaiting_grant.lock().unwrap().insert(kind);
+    }
+
+    /// Refresh `kind` right away and wake the watchers of it, called when a framework reports a change itself.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn push<R: Runtime>(&self, app_handle: &AppHandle<R>, kind: PermissionKind) {
+        self.refresh(app_handle, kind);
+
+        let _ = self.pushes.send(kind);
+    }
+
+    /// Sleep for `interval`, returning early once a change of one of `kinds` is pushed by its framework.
+    pub(crate) async fn tick(&self, kinds: &[PermissionKind], interval: Duration) {
+        let mut pushes = self.pushes.subscribe();
+
+        let _ = tokio::time::timeout(interval, async {
+            loop {
+                match pushes.recv().await {
+                    Ok(kind) if kinds.contains(&kind) => return,
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => std::future::pending().await,
+                }
+            }
+        })
+        .await;
+    }
+
+    /// Query the system for the status of `kinds`, with the ones that are not granted.
+    pub fn summary<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        kinds: &[PermissionKind],
+    ) -> StatusSummary {
+        let permissions: Vec<_> = kinds
+            .iter()
+            .map(|&kind| PermissionStatusEntry {
+                kind,
+                status: self.refresh(app_handle, kind),
+            })
+            .collect();
+
+        let missing: Vec<_> = permissions
+            .iter()
+            .filter(|entry| !entry.status.is_granted())
+            .map(|entry| entry.kind)
+            .collect();
+
+        StatusSummary {
+            ok: missing.is_empty(),
+            permissions,
+            missing,
+        }
+    }
+
+    /// Stream the changes of `kind`, polled every `watchInterval` while a stream is alive.
+    ///
+    /// # Example
+    /// ```
+    /// use futures_util::StreamExt;
+    /// use tauri_plugin_macos_permissions::{PermissionKind, PermissionsManager};
+    ///
+    /// let mut changes = app.state::<PermissionsManager>().subscribe(PermissionKind::Accessibility);
+    ///
+    /// while let Some(change) = changes.next().await {
+    ///     println!("Status: {:?}", change.status); // Granted
+    /// }
+    /// ```
+    pub fn subscribe(&self, kind: PermissionKind) -> impl Stream<Item = StatusChange> + Send {
+        *self.subscribed.lock().unwrap().entry(kind).or_default() += 1;
+
+        let subscription = Subscription {
+            kind,
+            subscribed: self.subscribed.clone(),
+        };
+
+        stream::unfold(
+            (self.changes.subscribe(), subscription),
+            move |(mut receiver, subscription)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(change) if change.kind == kind => {
+                            return Some((change, (receiver, subscription)))
+                        }
+                        Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )
+    }
+
+    /// Poll the subscribed permissions for the streams returned by [`subscribe`](Self::subscribe), and the ones awaiting a grant.
+    pub(crate) fn spawn_poller<R: Runtime>(app_handle: AppHandle<R>) {
+        tauri::async_runtime::spawn(async move {
+            let manager = app_handle.state::<PermissionsManager>();
+            let interval = Duration::from_millis(manager.config.watch_interval);
+
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let mut kinds = manager.awaiting_grant.lock().unwrap().clone();
+
+                if manager.changes.receiver_count() > 0 {
+                    kinds.extend(manager.subscribed.lock().unwrap().keys());
+                }
+
+                for kind in kinds {
+                    manager.refresh(&app_handle, kind);
+                }
+            }
+        });
+    }
+}
+
+/// Counts a stream of [`PermissionsManager::subscribe`], so the poller stops refreshing its kind once it is dropped.
+struct Subscription {
+    kind: PermissionKind,
+    subscribed: Arc<Mutex<HashMap<PermissionKind, usize>>>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut subscribed = self.subscribed.lock().unwrap();
+
+        if let Some(count) = subscribed.get_mut(&self.kind) {
+            *count -= 1;
+
+            if *count == 0 {
+                subscribed.remove(&self.kind);
+            }
+        }
+    }
+}
+
+/// Bring the `main` window, or any window without one, back to the front.
+fn focus_main_window<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    let window = app_handle
+        .get_webview_window("main")
+        .or_else(|| app_handle.webview_windows().into_values().next());
+
+    let Some(window) = window else {
+        return false;
+    };
+
+    let _ = window.unminimize();
+    let _ = window.show();
+
+    window.set_focus().is_ok()
+}
@@ -0,0 +1,146 @@
+// The rendering of PPPC profiles, included by `build.rs` so it must not use the rest of the crate.
+
+/// The permissions a PPPC profile can grant, with their service key and authorization.
+///
+/// Reference: https://developer.apple.com/documentation/devicemanagement/privacypreferencespolicycontrol/services
+pub const PPPC_SERVICES: [(&str, &str, &str); 4] = [
+    ("accessibility", "Accessibility", "Allow"),
+    ("fullDiskAccess", "SystemPolicyAllFiles", "Allow"),
+    (
+        "screenRecording",
+        "ScreenCapture",
+        "AllowStandardUserToSetSystemService",
+    ),
+    (
+        "inputMonitoring",
+        "ListenEvent",
+        "AllowStandardUserToSetSystemService",
+    ),
+];
+
+/// The PPPC service key and authorization of `permission`, e.g. `fullDiskAccess`, `None` if a profile cannot grant it.
+pub fn pppc_service(permission: &str) -> Option<(&'static str, &'static str)> {
+    PPPC_SERVICES
+        .iter()
+        .find(|&&(name, _, _)| name == permission)
+        .map(|&(_, service, authorization)| (service, authorization))
+}
+
+/// Render a `.mobileconfig` granting `services`, as returned by [`pppc_service`], to the app `identifier`.
+///
+/// The organization defaults to the identifier.
+pub fn render_mobileconfig(
+    identifier: &str,
+    code_requirement: &str,
+    organization: Option<&str>,
+    services: &[(&str, &str)],
+) -> String {
+    let profile_uuid = uuid(&[identifier, "profile"]);
+    let payload_uuid = uuid(&[identifier, "pppc"]);
+
+    let organization = escape(organization.unwrap_or(identifier));
+    let identifier = escape(identifier);
+    let code_requirement = escape(code_requirement);
+
+    let services: String = services
+        .iter()
+        .map(|(service, authorization)| {
+            format!(
+                r#"
+				<key>{service}</key>
+				<array>
+					<dict>
+						<key>Identifier</key>
+						<string>{identifier}</string>
+						<key>IdentifierType</key>
+						<string>bundleID</string>
+						<key>CodeRequirement</key>
+						<string>{code_requirement}</string>
+						<key>StaticCode</key>
+						<false/>
+						<key>Authorization</key>
+						<string>{authorization}</string>
+					</dict>
+				</array>"#
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>PayloadContent</key>
+	<array>
+		<dict>
+			<key>PayloadDisplayName</key>
+			<string>Privacy Preferences Policy Control</string>
+			<key>PayloadIdentifier</key>
+			<string>{identifier}.pppc.{payload_uuid}</string>
+			<key>PayloadType</key>
+			<string>com.apple.TCC.configuration-profile-policy</string>
+			<key>PayloadUUID</key>
+			<string>{payload_uuid}</string>
+			<key>PayloadVersion</key>
+			<integer>1</integer>
+			<key>Services</key>
+			<dict>{services}
+			</dict>
+		</dict>
+	</array>
+	<key>PayloadDisplayName</key>
+	<string>{identifier} Permissions</string>
+	<key>PayloadIdentifier</key>
+	<string>{identifier}.pppc</string>
+	<key>PayloadOrganization</key>
+	<string>{organization}</string>
+	<key>PayloadScope</key>
+	<string>System</string>
+	<key>PayloadType</key>
+	<string>Configuration</string>
+	<key>PayloadUUID</key>
+	<string>{profile_uuid}</string>
+	<key>PayloadVersion</key>
+	<integer>1</integer>
+</dict>
+</plist>
+"#
+    )
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A UUID derived from `parts`, so regenerating the profile replaces the installed one.
+pub fn uuid(parts: &[&str]) -> String {
+    // FNV-1a, stable across Rust versions unlike `DefaultHasher`.
+    let hash = |seed: u64| {
+        parts
+            .iter()
+            .flat_map(|part| part.bytes().chain([0]))
+            .fold(seed, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            })
+    };
+
+    let hex = format!(
+        "{:016X}{:016X}",
+        hash(0xcbf29ce484222325),
+        hash(0x84222325cbf29ce4)
+    );
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
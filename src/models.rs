@@ -0,0 +1,766 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::{commands::*, error::Error};
+
+/// The macos permissions supported by the plugin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum PermissionKind {
+    Accessibility,
+    FullDiskAccess,
+    ScreenRecording,
+    Microphone,
+    Camera,
+    InputMonitoring,
+    Location,
+    Notifications,
+}
+
+impl PermissionKind {
+    /// All permission kinds, in the order they are documented.
+    pub const ALL: [PermissionKind; 8] = [
+        PermissionKind::Accessibility,
+        PermissionKind::FullDiskAccess,
+        PermissionKind::ScreenRecording,
+        PermissionKind::Microphone,
+        PermissionKind::Camera,
+        PermissionKind::InputMonitoring,
+        PermissionKind::Location,
+        PermissionKind::Notifications,
+    ];
+
+    /// The name of the permission in System Settings.
+    pub fn label(self) -> &'static str {
+        match self {
+            PermissionKind::Accessibility => "Accessibility",
+            PermissionKind::FullDiskAccess => "Full Disk Access",
+            PermissionKind::ScreenRecording => "Screen Recording",
+            PermissionKind::Microphone => "Microphone",
+            PermissionKind::Camera => "Camera",
+            PermissionKind::InputMonitoring => "Input Monitoring",
+            PermissionKind::Location => "Location Services",
+            PermissionKind::Notifications => "Notifications",
+        }
+    }
+
+    /// Check whether the permission is granted.
+    ///
+    /// Checks never prompt, open System Settings or register the app with TCC, they are safe to poll.
+    pub async fn check<R: Runtime>(self, app_handle: &AppHandle<R>) -> bool {
+        self.check_blocking(app_handle)
+    }
+
+    /// Blocking variant of [`check`](Self::check).
+    pub fn check_blocking<R: Runtime>(self, app_handle: &AppHandle<R>) -> bool {
+        match self {
+            PermissionKind::Accessibility => check_accessibility_permission_blocking(),
+            PermissionKind::FullDiskAccess => {
+                check_full_disk_access_permission_blocking(app_handle)
+            }
+            PermissionKind::ScreenRecording => check_screen_recording_permission_blocking(),
+            PermissionKind::Microphone => check_microphone_permission_blocking(),
+            PermissionKind::Camera => check_camera_permission_blocking(),
+            PermissionKind::InputMonitoring => check_input_monitoring_permission_blocking(),
+            PermissionKind::Location => check_location_permission_blocking(),
+            PermissionKind::Notifications => check_notification_permission_blocking(),
+        }
+    }
+
+    /// Request the permission, showing the system prompt or opening System Settings.
+    pub async fn request<R: Runtime>(
+        self,
+        app_handle: &AppHandle<R>,
+    ) -> Result<RequestOutcome, Error> {
+        let app_handle = app_handle.clone();
+
+        match self {
+            PermissionKind::Accessibility => {
+                request_accessibility_permission(app_handle, None).await
+            }
+            PermissionKind::FullDiskAccess => request_full_disk_access_permission(app_handle).await,
+            PermissionKind::ScreenRecording => {
+                request_screen_recording_permission(app_handle).await
+            }
+            PermissionKind::Microphone => request_microphone_permission(app_handle).await,
+            PermissionKind::Camera => request_camera_permission(app_handle).await,
+            PermissionKind::InputMonitoring => {
+                request_input_monitoring_permission(app_handle).await
+            }
+            PermissionKind::Location => request_location_permission(app_handle).await,
+            PermissionKind::Notifications => request_notification_permission(app_handle).await,
+        }
+    }
+
+    /// Open the System Settings pane where the permission is granted, without showing a prompt.
+    pub fn open_settings(self) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        return crate::macos::open_permission_settings(self);
+
+        #[cfg(not(target_os = "macos"))]
+        return Ok(());
+    }
+}
+
+/// Payload of the [`PERMISSION_CHANGED_EVENT`](crate::PERMISSION_CHANGED_EVENT) event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PermissionChanged {
+    /// The id of the watcher that detected the change.
+    pub id: u32,
+    pub kind: PermissionKind,
+    pub authorized: bool,
+}
+
+/// Payload of the [`PERMISSIONS_CHANGED_EVENT`](crate::PERMISSIONS_CHANGED_EVENT) event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PermissionsChanged {
+    /// The changes detected together, in the order they were detected.
+    pub changes: Vec<PermissionChanged>,
+}
+
+/// The authorization status of a permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum PermissionStatus {
+    /// The permission is granted.
+    Granted,
+    /// The user denied the permission.
+    Denied,
+    /// The user has not been asked yet.
+    NotDetermined,
+    /// The permission is restricted, e.g. by parental controls.
+    Restricted,
+    /// The permission cannot be determined, e.g. without a GUI session.
+    Unsupported,
+}
+
+impl PermissionStatus {
+    pub fn is_granted(self) -> bool {
+        self == PermissionStatus::Granted
+    }
+}
+
+/// The raw values the status was derived from, useful when debugging.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PermissionDetails {
+    /// Result of `AXIsProcessTrusted`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trusted: Option<bool>,
+    /// The full disk access probe path that could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probe_path: Option<String>,
+    /// Result of `CGPreflightScreenCaptureAccess`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preflight: Option<bool>,
+    /// The raw `AVAuthorizationStatus`, `CLAuthorizationStatus` or `UNAuthorizationStatus`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_status: Option<i32>,
+    /// The raw `IOHIDAccessType`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hid_access: Option<u32>,
+}
+
+/// The state of a permission returned by `check_permission`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PermissionState {
+    pub kind: PermissionKind,
+    pub status: PermissionStatus,
+    /// Whether a matching capture device is connected, only set for microphone and camera.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_connected: Option<bool>,
+    /// Whether a configuration profile controls the permission, so the user cannot change it.
+    /// Not set if this cannot be determined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<PermissionDetails>,
+}
+
+/// What happened when a permission was requested, returned by every request command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum RequestOutcome {
+    /// The permission is granted, either already or by the user responding to the prompt.
+    Granted,
+    /// The user denied the permission.
+    Denied,
+    /// The prompt was closed without granting the permission.
+    Dismissed,
+    /// System Settings was opened, the user has to grant the permission there.
+    OpenedSettings,
+    /// The prompt is no longer available, e.g. because the user was already asked.
+    PromptUnavailable,
+    /// The user is connected remotely and may not see the prompt, see `check_remote_session`.
+    RemoteSession,
+    /// The permission cannot be requested on this platform.
+    Unsupported,
+}
+
+impl RequestOutcome {
+    pub fn is_granted(self) -> bool {
+        self == RequestOutcome::Granted
+    }
+}
+
+/// Options of `request_accessibility_permission`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct AccessibilityRequestOptions {
+    /// Show the system prompt, it only appears once per install. Defaults to `true`.
+    pub prompt: bool,
+    /// Open the Accessibility pane of System Settings, ignored with the `headlessRequests` config or the `prompt` request strategy. Defaults to `false`.
+    pub open_settings: bool,
+}
+
+impl Default for AccessibilityRequestOptions {
+    fn default() -> Self {
+        Self {
+            prompt: true,
+            open_settings: false,
+        }
+    }
+}
+
+/// Options of `ensure_permission`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct EnsurePermissionOptions {
+    /// Request the permission if it is missing, following its request strategy. Defaults to `true`.
+    pub request: bool,
+    /// Wait until the permission is granted after System Settings was opened, bounded by the `requestTimeout`
+    /// config. Defaults to `false`.
+    pub wait: bool,
+}
+
+impl Default for EnsurePermissionOptions {
+    fn default() -> Self {
+        Self {
+            request: true,
+            wait: false,
+        }
+    }
+}
+
+/// The result of `ensure_permission`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct EnsuredPermission {
+    pub kind: PermissionKind,
+    /// Whether the permission is granted in the end.
+    pub granted: bool,
+    /// The status of the permission in the end.
+    pub status: PermissionStatus,
+    /// What the request did, missing if the permission was already granted or not requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<RequestOutcome>,
+}
+
+/// The type of capture devices listed by `list_capture_devices`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum MediaType {
+    /// Microphones, gated by microphone permission.
+    Audio,
+    /// Cameras, gated by camera permission.
+    Video,
+}
+
+impl MediaType {
+    /// The permission gating access to the devices.
+    pub fn permission(self) -> PermissionKind {
+        match self {
+            MediaType::Audio => PermissionKind::Microphone,
+            MediaType::Video => PermissionKind::Camera,
+        }
+    }
+}
+
+/// A capture device returned by `list_capture_devices`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct CaptureDevice {
+    /// The `uniqueID` of the device, stable across launches.
+    pub id: String,
+    pub name: String,
+    pub model_id: String,
+}
+
+/// Whether a permission actually works, returned by the `verify_*_access` commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum VerificationStatus {
+    /// The permission is granted and works.
+    Granted,
+    /// The permission is granted, but the protected resource is still not accessible.
+    Ineffective,
+    /// The permission is not granted.
+    Denied,
+}
+
+/// The result of the `verify_*_access` commands.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct Verification {
+    pub status: VerificationStatus,
+    /// Why the permission is not effective.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Verification {
+    pub fn granted() -> Self {
+        Self {
+            status: VerificationStatus::Granted,
+            reason: None,
+        }
+    }
+
+    pub fn ineffective(reason: impl Into<String>) -> Self {
+        Self {
+            status: VerificationStatus::Ineffective,
+            reason: Some(reason.into()),
+        }
+    }
+
+    pub fn denied() -> Self {
+        Self {
+            status: VerificationStatus::Denied,
+            reason: None,
+        }
+    }
+}
+
+/// The result of `check_event_tap_readiness`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct EventTapReadiness {
+    /// Whether a keyboard event tap can be installed.
+    pub ready: bool,
+    pub accessibility: bool,
+    pub input_monitoring: bool,
+    /// Whether a test event tap could be created.
+    pub event_tap: bool,
+    /// The permissions that still have to be granted.
+    pub missing: Vec<PermissionKind>,
+}
+
+/// A predefined set of permissions for a common kind of app, checked by `check_profile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum Profile {
+    /// Screen recording and microphone.
+    ScreenRecorder,
+    /// Accessibility, to paste into the frontmost app.
+    ClipboardManager,
+    /// Accessibility, input monitoring and screen recording.
+    AutomationTool,
+    /// Microphone, camera, screen recording and notifications.
+    MeetingApp,
+}
+
+impl Profile {
+    /// The permissions the profile requires.
+    pub fn permissions(self) -> &'static [PermissionKind] {
+        match self {
+            Profile::ScreenRecorder => {
+                &[PermissionKind::ScreenRecording, PermissionKind::Microphone]
+            }
+            Profile::ClipboardManager => &[PermissionKind::Accessibility],
+            Profile::AutomationTool => &[
+                PermissionKind::Accessibility,
+                PermissionKind::InputMonitoring,
+                PermissionKind::ScreenRecording,
+            ],
+            Profile::MeetingApp => &[
+                PermissionKind::Microphone,
+                PermissionKind::Camera,
+                PermissionKind::ScreenRecording,
+                PermissionKind::Notifications,
+            ],
+        }
+    }
+}
+
+/// The result of `check_profile`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ProfileReadiness {
+    pub profile: Profile,
+    /// Whether every permission of the profile is granted.
+    pub ready: bool,
+    pub granted: Vec<PermissionKind>,
+    pub missing: Vec<PermissionKind>,
+}
+
+/// Where the app runs from, returned by `check_app_location`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct AppLocation {
+    /// The path of the app bundle.
+    pub path: String,
+    /// Whether Gatekeeper runs the app from a randomized read-only copy, grants to it do not persist.
+    pub translocated: bool,
+    /// Whether the app still carries the quarantine attribute of a download.
+    pub quarantined: bool,
+}
+
+/// When TCC recorded a decision of the app, returned by `get_prompt_history`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PromptHistory {
+    pub kind: PermissionKind,
+    /// Milliseconds since the Unix epoch the user answered the prompt, unknown once the decision was changed in System Settings since.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_prompted: Option<u64>,
+    /// Milliseconds since the Unix epoch the decision was last changed, by answering the prompt or in System Settings.
+    pub last_modified: u64,
+    /// Milliseconds since the Unix epoch macOS last reminded the user of the grant, as it does monthly for screen recording.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_reminded: Option<u64>,
+}
+
+/// Who the running app is to TCC, returned by `get_app_identity`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct AppIdentity {
+    /// The path of the running executable.
+    pub executable_path: String,
+    /// The bundle identifier, missing for a bare binary, e.g. from `cargo run`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    /// The team identifier of the signing certificate, missing for ad-hoc and unsigned builds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
+    /// The signing certificate, `adhoc` for ad-hoc signed builds, missing for unsigned ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_identity: Option<String>,
+    /// Whether the app runs from a `.app` bundle, grants to a bare binary are given to the terminal running it instead.
+    pub bundled: bool,
+}
+
+/// The process TCC attributes the permissions of the app to, returned by `get_responsible_process`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ResponsibleProcess {
+    pub pid: i32,
+    /// The executable path of the process.
+    pub path: String,
+    /// The outermost app bundle containing it, e.g. `/System/Applications/Utilities/Terminal.app`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_path: Option<String>,
+    /// The bundle identifier of that bundle, e.g. `com.apple.Terminal`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    /// Whether the app is responsible for itself, the grants listed for it in System Settings are then its own.
+    pub is_self: bool,
+    /// What to tell the developer when another process is responsible, missing otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guidance: Option<String>,
+}
+
+/// A copy of the app known to LaunchServices, see [`AppCopies`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct AppCopy {
+    /// The path of the app bundle.
+    pub path: String,
+    /// The `CFBundleShortVersionString` of the copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Whether the running process is this copy.
+    pub current: bool,
+    /// Whether the copy is signed like the running one, so TCC applies the same grants to both.
+    /// Missing when the signatures cannot be read, e.g. with the `app-store` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shares_grants: Option<bool>,
+}
+
+/// The copies of the app installed side by side, returned by `find_app_copies`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct AppCopies {
+    /// Every copy with the bundle identifier of the running app, the running one included.
+    pub copies: Vec<AppCopy>,
+    /// The path of the copy the user most likely granted the permissions to, when it is not the running one.
+    ///
+    /// A guess: the copy in an `Applications` folder, if the running one is elsewhere and does not share its grants.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub likely_granted: Option<String>,
+}
+
+/// How the plugin was built and where it runs, returned by `get_plugin_info`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PluginInfo {
+    /// The version of the plugin, e.g. `2.3.0`.
+    pub version: String,
+    /// The macOS SDK the plugin was built against, missing when it was built without Xcode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdk_version: Option<String>,
+    /// The `MACOSX_DEPLOYMENT_TARGET` the plugin was built with, missing when it was not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployment_target: Option<String>,
+    /// The enabled features of the plugin, e.g. `["app-store", "specta"]`.
+    pub features: Vec<String>,
+    /// The running macOS version, e.g. `15.1`, missing on other platforms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+    /// Whether System Integrity Protection is enabled, missing when it cannot be checked, see `is_sip_enabled`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sip_enabled: Option<bool>,
+}
+
+/// Counters of the current session, returned by `get_permission_stats`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PermissionStats {
+    /// Status queries to the system, by checks, watchers and polling.
+    pub checks: u64,
+    /// System prompts shown by requests.
+    pub prompts_shown: u64,
+    /// Times System Settings was opened by requests, onboarding, the tray menu or `open_settings_and_wait`.
+    pub settings_opened: u64,
+    /// Permissions seen changing to granted.
+    pub grants_observed: u64,
+    /// Milliseconds since the plugin was set up.
+    pub elapsed: u64,
+}
+
+/// How the user is connected to the Mac remotely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum RemoteSessionKind {
+    /// The app was started from an SSH session.
+    Ssh,
+    /// The screen is shared with Screen Sharing.
+    ScreenSharing,
+    /// The Mac is controlled with Apple Remote Desktop.
+    RemoteManagement,
+}
+
+impl RemoteSessionKind {
+    /// What the user should do to grant permissions, ready to be shown.
+    pub fn guidance(self) -> &'static str {
+        match self {
+            RemoteSessionKind::Ssh => "The app was started over SSH, permission prompts only appear on the Mac's own display. Start the app on the Mac itself to grant permissions.",
+            RemoteSessionKind::ScreenSharing => "Screen Sharing is active, permission prompts may not be shown to you. Grant the permissions on the Mac itself, or in System Settings > Privacy & Security.",
+            RemoteSessionKind::RemoteManagement => "The Mac is controlled with Remote Management, permission prompts may not be shown to you. Grant the permissions on the Mac itself, or deploy them with a PPPC profile.",
+        }
+    }
+}
+
+/// A remote session permission prompts may not be presentable in, returned by `check_remote_session`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct RemoteSession {
+    /// How the user is connected.
+    pub kind: RemoteSessionKind,
+    /// What the user should do to grant permissions, see [`RemoteSessionKind::guidance`].
+    pub guidance: String,
+}
+
+impl From<RemoteSessionKind> for RemoteSession {
+    fn from(kind: RemoteSessionKind) -> Self {
+        RemoteSession {
+            kind,
+            guidance: kind.guidance().to_string(),
+        }
+    }
+}
+
+/// Why global shortcuts cannot be registered yet, returned by `check_global_shortcut_readiness`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum ShortcutBlocker {
+    /// There is no window server session, e.g. over SSH.
+    NoGuiSession,
+    AccessibilityMissing,
+    InputMonitoringMissing,
+    /// Both permissions are granted, but the event tap still could not be created.
+    EventTapUnavailable,
+}
+
+/// The result of `check_global_shortcut_readiness`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ShortcutReadiness {
+    pub ready: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocker: Option<ShortcutBlocker>,
+}
+
+/// A message of `subscribe_permission_updates`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum PermissionUpdate {
+    /// The status of every subscribed permission, sent first.
+    Snapshot {
+        statuses: Vec<PermissionStatusEntry>,
+    },
+    /// A subscribed permission changed.
+    Changed(StatusChange),
+    /// Several subscribed permissions changed within the `coalesceWindow` config.
+    Batch { changes: Vec<StatusChange> },
+}
+
+/// A change of a permission status, see [`PermissionsManager::subscribe`](crate::PermissionsManager::subscribe).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct StatusChange {
+    pub kind: PermissionKind,
+    pub status: PermissionStatus,
+    pub previous: PermissionStatus,
+}
+
+/// The status of one permission in a [`PermissionUpdate::Snapshot`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PermissionStatusEntry {
+    pub kind: PermissionKind,
+    pub status: PermissionStatus,
+}
+
+/// A compact overview of several permissions, returned by `get_status_summary`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct StatusSummary {
+    /// Whether every permission is granted, `false` if any needs attention.
+    pub ok: bool,
+    pub permissions: Vec<PermissionStatusEntry>,
+    pub missing: Vec<PermissionKind>,
+}
+
+impl StatusSummary {
+    /// Whether `kind` is listed and granted.
+    pub fn is_granted(&self, kind: PermissionKind) -> bool {
+        self.permissions
+            .iter()
+            .any(|entry| entry.kind == kind && entry.status.is_granted())
+    }
+}
+
+/// The payload of the event emitted on the first launch after a macOS major upgrade, returned by `get_os_upgrade`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct OsUpgraded {
+    /// The version of the last launch, e.g. `14.6`.
+    pub previous_version: String,
+    /// The running version, e.g. `15.1`.
+    pub version: String,
+    /// The re-verified status of the permissions of the `permissions` config.
+    pub summary: StatusSummary,
+}
+
+/// The payload of the event emitted when the onboarding window is closed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct OnboardingFinished {
+    /// Whether all listed permissions were granted.
+    pub completed: bool,
+    /// The listed permissions that are still not granted.
+    pub missing: Vec<PermissionKind>,
+}
+
+/// The payload of the event emitted when a permission is granted after a request opened System Settings.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct GrantCompleted {
+    pub kind: PermissionKind,
+    /// Whether the main window was brought back to the front, see the `refocusOnGrant` config.
+    pub refocused: bool,
+}
+
+/// The payload of the event emitted when the user should be reminded about a missing permission.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct Reminder {
+    pub kind: PermissionKind,
+    /// How many times the user has been reminded, including this one.
+    pub count: u32,
+}
+
+/// Whether a path can be read, and which access is missing otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum PathAccess {
+    /// The path can be read.
+    Readable,
+    /// The path is in the Desktop, Documents or Downloads folder, iCloud Drive or on another volume, and the user
+    /// denied access to it. It is granted in the Files & Folders pane of System Settings.
+    FilesAndFolders,
+    /// The path is protected beyond Files & Folders, e.g. the data of another app, only full disk access reads it.
+    FullDiskAccess,
+    /// The app sandbox keeps the app out, the user has to select the path in an open panel instead.
+    Sandbox,
+    /// The path cannot be read for another reason, e.g. its file system permissions.
+    Denied,
+    /// The path does not exist.
+    Missing,
+}
+
+/// The access of a single path, returned by `request_path_access`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct PathAccessEntry {
+    pub path: String,
+    pub access: PathAccess,
+}
+
+/// The payload of the event emitted once the access of dropped files was checked, see the `fileDrop` config.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct FileDropResolved {
+    /// The label of the window or webview the files were dropped on.
+    pub label: String,
+    /// The access of each dropped path.
+    pub paths: Vec<PathAccessEntry>,
+    /// Whether all dropped paths can be read.
+    pub ready: bool,
+    /// What the request of the missing access did, missing if nothing was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<RequestOutcome>,
+}
@@ -0,0 +1,97 @@
+use std::{
+    sync::{mpsc, OnceLock},
+    time::Duration,
+};
+
+use block2::RcBlock;
+use objc2::{
+    class, msg_send,
+    rc::Retained,
+    runtime::{AnyObject, Bool},
+};
+use objc2_foundation::NSBundle;
+
+use crate::{
+    macos::{catch_exception, Shared},
+    prompt::prompting,
+};
+
+#[link(name = "UserNotifications", kind = "framework")]
+extern "C" {}
+
+/// `UNAuthorizationOptionBadge | UNAuthorizationOptionSound | UNAuthorizationOptionAlert`.
+const AUTHORIZATION_OPTIONS: usize = 0b111;
+
+/// The notification center of the app, looked up once.
+fn notification_center() -> Option<&'static AnyObject> {
+    static CENTER: OnceLock<Option<Shared<AnyObject>>> = OnceLock::new();
+
+    CENTER
+        .get_or_init(|| {
+            // UNUserNotificationCenter raises an exception when the process is not an app bundle.
+            NSBundle::mainBundle().bundleIdentifier()?;
+
+            let center: Option<Retained<AnyObject>> = catch_exception(|| unsafe {
+                msg_send![class!(UNUserNotificationCenter), currentNotificationCenter]
+            })
+            .ok()
+            .flatten();
+
+            // The notification center is thread safe.
+            center.map(|center| unsafe { Shared::new(center) })
+        })
+        .as_deref()
+}
+
+/// Returns the raw `UNAuthorizationStatus`, or `None` outside of an app bundle.
+pub fn authorization_status() -> Option<isize> {
+    #[cfg(feature = "swift")]
+    if let Some(status) = crate::swift::notification_authorization_status() {
+        return Some(status);
+    }
+
+    let center = notification_center()?;
+    let (sender, receiver) = mpsc::channel();
+
+    let completion_handler = RcBlock::new(move |settings: *mut AnyObject| {
+        let status: Result<isize, String> =
+            catch_exception(|| unsafe { msg_send![settings, authorizationStatus] });
+
+        if let Ok(status) = status {
+            let _ = sender.send(status);
+        }
+    });
+
+    catch_exception(|| unsafe {
+        let _: () = msg_send![
+            &*center,
+            getNotificationSettingsWithCompletionHandler: &*completion_handler
+        ];
+    })
+    .ok()?;
+
+    receiver.recv_timeout(Duration::from_secs(5)).ok()
+}
+
+/// Show the notification prompt and wait for the user's choice.
+pub fn request_authorization() -> Result<bool, String> {
+    let center =
+        notification_center().ok_or("Notifications are only available in an app bundle")?;
+    let (sender, receiver) = mpsc::channel();
+
+    let completion_handler = RcBlock::new(move |granted: Bool, _error: *mut AnyObject| {
+        let _ = sender.send(granted.as_bool());
+    });
+
+    prompting(|| {
+        catch_exception(|| unsafe {
+            let _: () = msg_send![
+                &*center,
+                requestAuthorizationWithOptions: AUTHORIZATION_OPTIONS,
+                completionHandler: &*completion_handler
+            ];
+        })
+    })?;
+
+    receiver.recv().map_err(|error| error.to_string())
+}
@@ -0,0 +1,219 @@
+use std::{borrow::Cow, sync::Mutex, time::Duration};
+
+use serde::Serialize;
+use tauri::{
+    http::{header::CONTENT_TYPE, Request, Response},
+    webview::PageLoadEvent,
+    AppHandle, Manager, Runtime, UriSchemeContext, Url, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, WindowEvent,
+};
+
+use crate::{
+    manager::PermissionsManager,
+    models::{OnboardingFinished, PermissionKind, PermissionStatus},
+};
+
+/// Emitted when the onboarding window is closed, with an [`OnboardingFinished`] payload.
+pub const ONBOARDING_FINISHED_EVENT: &str = "macos-permissions://onboarding-finished";
+
+/// The URI scheme serving the onboarding page.
+pub(crate) const SCHEME: &str = "macos-permissions-onboarding";
+
+const LABEL: &str = "macos-permissions-onboarding";
+
+const PAGE: &str = include_str!("onboarding.html");
+
+/// The watcher ids of the tasks started for the onboarding window, stopped once it is closed.
+static TASKS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+#[derive(Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct OnboardingItem {
+    kind: PermissionKind,
+    label: &'static str,
+    status: PermissionStatus,
+}
+
+#[derive(Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct OnboardingState {
+    permissions: Vec<OnboardingItem>,
+    completed: bool,
+}
+
+/// Serve the onboarding page, its actions are intercepted before they reach the protocol.
+pub(crate) fn protocol<R: Runtime>(
+    _context: UriSchemeContext<'_, R>,
+    _request: Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
+    Response::builder()
+        .header(CONTENT_TYPE, "text/html")
+        .body(Cow::Borrowed(PAGE.as_bytes()))
+        .unwrap()
+}
+
+/// Open the onboarding window listing `kinds`, or focus it if it is already open.
+pub(crate) fn open<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kinds: Vec<PermissionKind>,
+) -> tauri::Result<()> {
+    if let Some(window) = app_handle.get_webview_window(LABEL) {
+        return window.set_focus();
+    }
+
+    // Windows and Android serve custom protocols over http.
+    let url = if cfg!(any(windows, target_os = "android")) {
+        format!("http://{SCHEME}.localhost/")
+    } else {
+        format!("{SCHEME}://localhost/")
+    };
+
+    let navigation_handle = app_handle.clone();
+    let page_kinds = kinds.clone();
+
+    let window = WebviewWindowBuilder::new(
+        app_handle,
+        LABEL,
+        WebviewUrl::CustomProtocol(Url::parse(&url).expect("invalid onboarding url")),
+    )
+    .title("Permissions")
+    .inner_size(480.0, 420.0)
+    .resizable(false)
+    .center()
+    .on_navigation(move |url| {
+        if url.path() == "/" {
+            return true;
+        }
+
+        handle_action(&navigation_handle, url.path());
+
+        false
+    })
+    .on_page_load(move |window, payload| {
+        if payload.event() == PageLoadEvent::Finished {
+            render(&window, &state(window.app_handle(), &page_kinds));
+        }
+    })
+    .build()?;
+
+    let event_handle = app_handle.clone();
+    let event_kinds = kinds.clone();
+
+    window.on_window_event(move |event| {
+        if !matches!(event, WindowEvent::Destroyed) {
+            return;
+        }
+
+        let watchers = event_handle
+            .state::<PermissionsManager>()
+            .inner()
+            .watchers();
+
+        // Pending requests and the render loop would otherwise outlive the window.
+        for id in TASKS.lock().unwrap().drain(..) {
+            watchers.unwatch(id);
+        }
+
+        let missing: Vec<_> = state(&event_handle, &event_kinds)
+            .permissions
+            .into_iter()
+            .filter(|item| !item.status.is_granted())
+            .map(|item| item.kind)
+            .collect();
+
+        event_handle.state::<PermissionsManager>().emit(
+            &event_handle,
+            ONBOARDING_FINISHED_EVENT,
+            OnboardingFinished {
+                completed: missing.is_empty(),
+                missing,
+            },
+        );
+    });
+
+    let manager = app_handle.state::<PermissionsManager>();
+    let app_handle = app_handle.clone();
+
+    let id = manager.watchers().spawn(|_| async move {
+        let manager = app_handle.state::<PermissionsManager>();
+        let interval = Duration::from_millis(manager.config().watch_interval);
+
+        let mut previous = state(&app_handle, &kinds);
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let Some(window) = app_handle.get_webview_window(LABEL) else {
+                break;
+            };
+
+            let current = state(&app_handle, &kinds);
+
+            if current != previous {
+                render(&window, &current);
+
+                previous = current;
+            }
+        }
+    });
+
+    TASKS.lock().unwrap().push(id);
+
+    Ok(())
+}
+
+fn state<R: Runtime>(app_handle: &AppHandle<R>, kinds: &[PermissionKind]) -> OnboardingState {
+    let manager = app_handle.state::<PermissionsManager>();
+
+    let permissions: Vec<_> = kinds
+        .iter()
+        .map(|&kind| OnboardingItem {
+            kind,
+            label: kind.label(),
+            status: manager.refresh(app_handle, kind),
+        })
+        .collect();
+
+    OnboardingState {
+        completed: permissions.iter().all(|item| item.status.is_granted()),
+        permissions,
+    }
+}
+
+fn render<R: Runtime>(window: &WebviewWindow<R>, state: &OnboardingState) {
+    if let Ok(state) = serde_json::to_string(state) {
+        let _ = window.eval(format!("window.__macosPermissionsRender({state})"));
+    }
+}
+
+/// Run an action of the page, `/request/<kind>`, `/settings/<kind>` or `/done`.
+fn handle_action<R: Runtime>(app_handle: &AppHandle<R>, path: &str) {
+    let mut segments = path.trim_start_matches('/').splitn(2, '/');
+
+    let action = segments.next().unwrap_or_default();
+    let kind = segments
+        .next()
+        .and_then(|kind| serde_json::from_value::<PermissionKind>(kind.into()).ok());
+
+    match (action, kind) {
+        ("request", Some(kind)) => {
+            let manager = app_handle.state::<PermissionsManager>();
+            let app_handle = app_handle.clone();
+
+            let id = manager.watchers().spawn(|_| async move {
+                let _ = kind.request(&app_handle).await;
+            });
+
+            TASKS.lock().unwrap().push(id);
+        }
+        ("settings", Some(kind)) => {
+            let _ = app_handle.state::<PermissionsManager>().open_settings(kind);
+        }
+        ("done", _) => {
+            if let Some(window) = app_handle.get_webview_window(LABEL) {
+                let _ = window.close();
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    mobileconfig::{self, render_mobileconfig},
+    models::PermissionKind,
+};
+
+/// A Privacy Preferences Policy Control profile, letting MDM pre-approve the app's permissions.
+///
+/// Only accessibility and full disk access can be granted by a profile. Screen recording and input
+/// monitoring can only be made grantable by standard users, the other permissions are left out.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{PermissionKind, PppcProfile};
+///
+/// let profile = PppcProfile {
+///     identifier: "com.example.app".into(),
+///     code_requirement: r#"identifier "com.example.app" and anchor apple generic"#.into(),
+///     permissions: vec![PermissionKind::Accessibility],
+///     organization: None,
+/// };
+///
+/// std::fs::write("app.mobileconfig", profile.to_mobileconfig())?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PppcProfile {
+    /// The bundle identifier of the app.
+    pub identifier: String,
+    /// The designated requirement of the app, as printed by `codesign -dr - <app>`.
+    pub code_requirement: String,
+    pub permissions: Vec<PermissionKind>,
+    pub organization: Option<String>,
+}
+
+impl PppcProfile {
+    /// Render the profile as a `.mobileconfig` property list.
+    pub fn to_mobileconfig(&self) -> String {
+        let services: Vec<_> = self
+            .permissions
+            .iter()
+            .filter_map(|&kind| pppc_service(kind))
+            .collect();
+
+        render_mobileconfig(
+            &self.identifier,
+            &self.code_requirement,
+            self.organization.as_deref(),
+            &services,
+        )
+    }
+}
+
+/// The PPPC service key and authorization for `kind`, `None` if a profile cannot grant it.
+fn pppc_service(kind: PermissionKind) -> Option<(&'static str, &'static str)> {
+    let serde_json::Value::String(permission) = serde_json::to_value(kind).ok()? else {
+        return None;
+    };
+
+    mobileconfig::pppc_service(&permission)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mobileconfig::uuid;
+
+    fn profile(permissions: Vec<PermissionKind>) -> PppcProfile {
+        PppcProfile {
+            identifier: "com.example.app".into(),
+            code_requirement: r#"identifier "com.example.app" and anchor apple generic"#.into(),
+            permissions,
+            organization: None,
+        }
+    }
+
+    #[test]
+    fn lists_the_services_a_profile_can_grant() {
+        let profile = profile(PermissionKind::ALL.to_vec()).to_mobileconfig();
+
+        for service in [
+            "<key>Accessibility</key>",
+            "<key>SystemPolicyAllFiles</key>",
+            "<key>ScreenCapture</key>",
+            "<key>ListenEvent</key>",
+        ] {
+            assert!(profile.contains(service), "{service}");
+        }
+
+        assert_eq!(profile.matches("<string>Allow</string>").count(), 2);
+        assert_eq!(
+            profile
+                .matches("<string>AllowStandardUserToSetSystemService</string>")
+                .count(),
+            2
+        );
+        assert!(!profile.contains("<key>Camera</key>"));
+    }
+
+    #[test]
+    fn escapes_the_code_requirement() {
+        let profile = profile(vec![PermissionKind::Accessibility]).to_mobileconfig();
+
+        assert!(profile.contains(
+            "<string>identifier &quot;com.example.app&quot; and anchor apple generic</string>"
+        ));
+        assert!(profile.contains("<string>com.example.app</string>"));
+    }
+
+    #[test]
+    fn keeps_its_uuids_across_renders() {
+        let first = profile(vec![PermissionKind::Accessibility]).to_mobileconfig();
+        let second = profile(vec![PermissionKind::FullDiskAccess]).to_mobileconfig();
+        let uuids = |profile: &str| {
+            profile
+                .lines()
+                .skip_while(|line| !line.contains("<key>PayloadUUID</key>"))
+                .nth(1)
+                .map(str::to_string)
+        };
+
+        assert_eq!(uuids(&first), uuids(&second));
+        assert_ne!(
+            uuid(&["com.example.app", "profile"]),
+            uuid(&["com.example.app", "pppc"])
+        );
+    }
+}
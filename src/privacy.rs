@@ -0,0 +1,99 @@
+// The privacy manifest entries of the plugin, included by `build.rs` so it must not use the rest of the crate.
+
+/// A required-reason API category the plugin calls, with the reasons that cover its use.
+///
+/// Reference: https://developer.apple.com/documentation/bundleresources/describing-use-of-required-reason-api
+pub struct AccessedApi {
+    /// The `NSPrivacyAccessedAPIType`, e.g. `NSPrivacyAccessedAPICategorySystemBootTime`.
+    pub category: &'static str,
+    pub reasons: &'static [&'static str],
+}
+
+/// The required-reason APIs the plugin calls.
+///
+/// A fixed list, the same with every feature: the calls are made by the core of the plugin, which no
+/// feature compiles out, and `bin` only adds a separate binary that is not bundled with the app.
+pub const ACCESSED_APIS: [AccessedApi; 2] = [
+    // `mach_absolute_time`, behind the timers of polling, timeouts and reminders.
+    AccessedApi {
+        category: "NSPrivacyAccessedAPICategorySystemBootTime",
+        reasons: &["35F9.1"],
+    },
+    // `stat`, on the reminders, the macOS version and the request lock files in the app data directory,
+    // and on the files dropped on a window with the `fileDrop` config.
+    AccessedApi {
+        category: "NSPrivacyAccessedAPICategoryFileTimestamp",
+        reasons: &["C617.1", "3B52.1"],
+    },
+];
+
+/// Render a `PrivacyInfo.xcprivacy` declaring the required-reason APIs the plugin calls.
+///
+/// The app has to merge it with the entries of its own code and other dependencies.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::privacy_manifest;
+///
+/// std::fs::write("PrivacyInfo.xcprivacy", privacy_manifest())?;
+/// ```
+pub fn privacy_manifest() -> String {
+    let apis: String = ACCESSED_APIS
+        .iter()
+        .map(|api| {
+            let reasons: String = api
+                .reasons
+                .iter()
+                .map(|reason| format!("\n\t\t\t\t<string>{reason}</string>"))
+                .collect();
+
+            format!(
+                r#"
+		<dict>
+			<key>NSPrivacyAccessedAPIType</key>
+			<string>{}</string>
+			<key>NSPrivacyAccessedAPITypeReasons</key>
+			<array>{reasons}
+			</array>
+		</dict>"#,
+                api.category
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>NSPrivacyTracking</key>
+	<false/>
+	<key>NSPrivacyTrackingDomains</key>
+	<array/>
+	<key>NSPrivacyCollectedDataTypes</key>
+	<array/>
+	<key>NSPrivacyAccessedAPITypes</key>
+	<array>{apis}
+	</array>
+</dict>
+</plist>
+"#
+    )
+}
+
+/// Returns the entries of [`privacy_manifest`] missing from an existing manifest, as `category: reason`.
+///
+/// Only looks for the strings, that is enough to catch a manifest that fell behind the plugin.
+pub fn missing_privacy_entries(manifest: &str) -> Vec<String> {
+    let declared = |value: &str| manifest.contains(&format!("<string>{value}</string>"));
+
+    ACCESSED_APIS
+        .iter()
+        .flat_map(|api| {
+            api.reasons
+                .iter()
+                .filter(move |reason| !declared(api.category) || !declared(reason))
+                .map(move |reason| format!("{}: {reason}", api.category))
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+#[cfg(all(test, target_os = "macos"))]
+use std::cell::Cell;
+
+#[cfg(all(test, target_os = "macos"))]
+thread_local! {
+    /// The prompting calls made on this thread, so the tests running in parallel don't count each other's.
+    static PROMPTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Run `prompt`, a call that may show a system prompt, a dialog or System Settings.
+///
+/// Every such call goes through here, so the tests can prove that checking a permission never reaches one.
+pub(crate) fn prompting<T>(prompt: impl FnOnce() -> T) -> T {
+    #[cfg(all(test, target_os = "macos"))]
+    PROMPTS.with(|prompts| prompts.set(prompts.get() + 1));
+
+    prompt()
+}
+
+/// The number of prompting calls made so far on this thread.
+#[cfg(all(test, target_os = "macos"))]
+pub(crate) fn prompts() -> usize {
+    PROMPTS.with(Cell::get)
+}
+
+// Off macOS every check is a stub, there is nothing to prove.
+#[cfg(all(test, target_os = "macos"))]
+mod tests {
+    use tauri::{async_runtime::block_on, test::mock_app, Manager};
+
+    use super::*;
+    use crate::{
+        commands::*, manager::PermissionsManager, models::PermissionKind, status::native_status,
+    };
+
+    #[test]
+    fn checks_never_prompt() {
+        let app = mock_app();
+
+        app.manage(PermissionsManager::default());
+
+        let app_handle = app.handle().clone();
+        let before = prompts();
+
+        for kind in PermissionKind::ALL {
+            native_status(kind);
+            kind.check_blocking(&app_handle);
+
+            block_on(check_permission(app_handle.clone(), kind, Some(true)));
+        }
+
+        block_on(async {
+            check_accessibility_permission().await;
+            check_full_disk_access_permission(app_handle.clone()).await;
+            check_screen_recording_permission().await;
+            check_microphone_permission().await;
+            check_camera_permission().await;
+            check_input_monitoring_permission().await;
+            check_location_permission().await;
+            check_notification_permission().await;
+            check_event_tap_readiness().await;
+            check_global_shortcut_readiness(Some(true)).await;
+            get_status_summary(app_handle.clone(), None).await;
+        });
+
+        assert_eq!(prompts(), before);
+
+        // The calls that do prompt are counted.
+        prompting(|| ());
+
+        assert_eq!(prompts(), before + 1);
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::{
+    config::ReminderConfig,
+    manager::PermissionsManager,
+    models::{PermissionKind, PermissionStatus, Reminder},
+};
+
+/// Emitted when the user should be reminded about a missing permission, with a [`Reminder`] payload.
+pub const REMINDER_EVENT: &str = "macos-permissions://reminder";
+
+const FILE_NAME: &str = "macos-permissions-reminders.json";
+
+/// How often the scheduler looks for due reminders, at most.
+const TICK: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReminderState {
+    count: u32,
+    /// Unix time in milliseconds.
+    next_at: u64,
+    dismissed: bool,
+}
+
+/// The reminder progress of each permission, persisted in the app data directory across launches.
+#[derive(Default)]
+pub struct Reminders {
+    path: Option<PathBuf>,
+    states: Mutex<HashMap<PermissionKind, ReminderState>>,
+}
+
+impl Reminders {
+    pub(crate) fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Self {
+        let path = app_handle
+            .path()
+            .app_data_dir()
+            .ok()
+            .map(|dir| dir.join(FILE_NAME));
+
+        let states = path
+            .as_ref()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            states: Mutex::new(states),
+        }
+    }
+
+    /// Never remind about `kind` again, e.g. when the user picks "Don't ask again".
+    pub fn dismiss(&self, kind: PermissionKind) {
+        let mut states = self.states.lock().unwrap();
+
+        states.entry(kind).or_default().dismissed = true;
+
+        self.save(&states);
+    }
+
+    /// Whether the user chose to never be reminded about `kind`.
+    pub fn is_dismissed(&self, kind: PermissionKind) -> bool {
+        self.states
+            .lock()
+            .unwrap()
+            .get(&kind)
+            .is_some_and(|state| state.dismissed)
+    }
+
+    /// Forget the reminders of `kind`, or of every permission, including "Don't ask again".
+    pub fn reset(&self, kind: Option<PermissionKind>) {
+        let mut states = self.states.lock().unwrap();
+
+        match kind {
+            Some(kind) => drop(states.remove(&kind)),
+            None => states.clear(),
+        }
+
+        self.save(&states);
+    }
+
+    /// Remind the user about the missing permissions of the `permissions` config while the app runs.
+    pub(crate) fn spawn_scheduler<R: Runtime>(app_handle: AppHandle<R>, config: ReminderConfig) {
+        tauri::async_runtime::spawn(async move {
+            let manager = app_handle.state::<PermissionsManager>();
+            let reminders = app_handle.state::<Reminders>();
+            let tick = TICK.min(Duration::from_millis(config.interval));
+
+            loop {
+                for &kind in &manager.config().permissions {
+                    let status = manager.refresh(&app_handle, kind);
+
+                    if let Some(reminder) = reminders.due(kind, status, &config) {
+                        manager.emit(&app_handle, REMINDER_EVENT, reminder);
+                    }
+                }
+
+                tokio::time::sleep(tick).await;
+            }
+        });
+    }
+
+    /// Record a reminder for `kind` if one is due, scheduling the next one with backoff.
+    fn due(
+        &self,
+        kind: PermissionKind,
+        status: PermissionStatus,
+        config: &ReminderConfig,
+    ) -> Option<Reminder> {
+        // Without a GUI session the status cannot be determined, there is nobody to remind.
+        if status == PermissionStatus::Unsupported {
+            return None;
+        }
+
+        let mut states = self.states.lock().unwrap();
+        let now = now();
+
+        if status.is_granted() {
+            // Start over if the permission is revoked later, unless the user opted out.
+            if states.get(&kind).is_some_and(|state| !state.dismissed) {
+                states.remove(&kind);
+
+                self.save(&states);
+            }
+
+            return None;
+        }
+
+        let state = states.entry(kind).or_insert_with(|| ReminderState {
+            next_at: now + config.interval,
+            ..Default::default()
+        });
+
+        if state.dismissed || now < state.next_at {
+            return None;
+        }
+
+        let delay = config.interval as f64 * config.backoff.powi(state.count as i32 + 1);
+
+        state.count += 1;
+        state.next_at = now + delay.min(config.max_interval as f64) as u64;
+
+        let reminder = Reminder {
+            kind,
+            count: state.count,
+        };
+
+        self.save(&states);
+
+        Some(reminder)
+    }
+
+    fn save(&self, states: &HashMap<PermissionKind, ReminderState>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        if let Ok(bytes) = serde_json::to_vec(states) {
+            let _ = fs::write(path, bytes);
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: ReminderConfig = ReminderConfig {
+        interval: 1000,
+        backoff: 2.0,
+        max_interval: 5000,
+    };
+
+    /// Make the next reminder of `kind` due, returning when it was scheduled relative to now.
+    fn elapse(reminders: &Reminders, kind: PermissionKind) -> i64 {
+        let mut states = reminders.states.lock().unwrap();
+        let state = states.get_mut(&kind).unwrap();
+        let next_at = state.next_at as i64 - now() as i64;
+
+        state.next_at = 0;
+
+        next_at
+    }
+
+    fn assert_about(delay: i64, expected: i64) {
+        assert!((expected - 100..=expected).contains(&delay), "{delay}");
+    }
+
+    #[test]
+    fn due_waits_for_the_interval_first() {
+        let reminders = Reminders::default();
+        let kind = PermissionKind::Camera;
+
+        assert!(reminders
+            .due(kind, PermissionStatus::Denied, &CONFIG)
+            .is_none());
+        assert_about(elapse(&reminders, kind), 1000);
+    }
+
+    #[test]
+    fn due_backs_off_up_to_the_max_interval() {
+        let reminders = Reminders::default();
+        let kind = PermissionKind::Camera;
+
+        reminders.due(kind, PermissionStatus::Denied, &CONFIG);
+        elapse(&reminders, kind);
+
+        for (count, delay) in [(1, 2000), (2, 4000), (3, 5000), (4, 5000)] {
+            let reminder = reminders
+                .due(kind, PermissionStatus::Denied, &CONFIG)
+                .unwrap();
+
+            assert_eq!(reminder.count, count);
+            assert_about(elapse(&reminders, kind), delay);
+        }
+    }
+
+    #[test]
+    fn due_starts_over_once_granted() {
+        let reminders = Reminders::default();
+        let kind = PermissionKind::Camera;
+
+        reminders.due(kind, PermissionStatus::Denied, &CONFIG);
+        elapse(&reminders, kind);
+        reminders.due(kind, PermissionStatus::Denied, &CONFIG);
+
+        assert!(reminders
+            .due(kind, PermissionStatus::Granted, &CONFIG)
+            .is_none());
+        assert!(!reminders.states.lock().unwrap().contains_key(&kind));
+    }
+
+    #[test]
+    fn due_skips_dismissed_and_unsupported_permissions() {
+        let reminders = Reminders::default();
+
+        reminders.dismiss(PermissionKind::Camera);
+
+        for (kind, status) in [
+            (PermissionKind::Camera, PermissionStatus::Denied),
+            (PermissionKind::Microphone, PermissionStatus::Unsupported),
+        ] {
+            reminders.states.lock().unwrap().entry(kind).or_default();
+
+            assert!(reminders.due(kind, status, &CONFIG).is_none());
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use tauri::{AppHandle, Runtime};
+
+use crate::{
+    commands::{
+        check_accessibility_permission_blocking, check_input_monitoring_permission_blocking,
+    },
+    models::{
+        PermissionDetails, PermissionKind, PermissionStatus, PermissionStatusEntry,
+        ShortcutBlocker, ShortcutReadiness,
+    },
+};
+
+#[cfg(target_os = "macos")]
+use {
+    crate::{location, macos::*, notifications},
+    macos_accessibility_client::accessibility::application_is_trusted,
+    std::path::{Path, PathBuf},
+    tauri::Manager,
+};
+
+/// Query the native APIs for the status of `kind`, along with the raw values it was derived from.
+#[cfg(target_os = "macos")]
+pub(crate) fn probe<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+) -> (PermissionStatus, PermissionDetails) {
+    probe_in(app_handle.path().home_dir().ok().as_deref(), kind)
+}
+
+/// [`probe`] without an `AppHandle`, full disk access is probed in `home_dir`.
+///
+/// Needs neither `NSApplication` nor the main thread.
+#[cfg(target_os = "macos")]
+fn probe_in(
+    home_dir: Option<&Path>,
+    kind: PermissionKind,
+) -> (PermissionStatus, PermissionDetails) {
+    let mut details = PermissionDetails::default();
+
+    // Full disk access only reads the file system, everything else needs a window server.
+    if kind != PermissionKind::FullDiskAccess && !has_gui_session() {
+        return (PermissionStatus::Unsupported, details);
+    }
+
+    let status = match kind {
+        PermissionKind::Accessibility => {
+            let trusted = application_is_trusted();
+
+            details.trusted = Some(trusted);
+
+            granted_or_denied(trusted)
+        }
+        PermissionKind::FullDiskAccess => {
+            let probe_path = home_dir.and_then(full_disk_access_probe);
+
+            details.probe_path = probe_path
+                .as_ref()
+                .map(|path| path.to_string_lossy().into_owned());
+
+            granted_or_denied(probe_path.is_some())
+        }
+        PermissionKind::ScreenRecording => {
+            let preflight = unsafe { CGPreflightScreenCaptureAccess() };
+
+            details.preflight = Some(preflight);
+
+            granted_or_denied(preflight)
+        }
+        PermissionKind::Microphone | PermissionKind::Camera => {
+            let media_type = media_type(kind).unwrap_or(AV_MEDIA_TYPE_AUDIO);
+
+            let authorization_status = authorization_status(media_type);
+
+            details.authorization_status = Some(authorization_status);
+
+            // Reference: https://developer.apple.com/documentation/avfoundation/avauthorizationstatus
+            match authorization_status {
+                0 => PermissionStatus::NotDetermined,
+                1 => PermissionStatus::Restricted,
+                AV_AUTHORIZATION_STATUS_AUTHORIZED => PermissionStatus::Granted,
+                _ => PermissionStatus::Denied,
+            }
+        }
+        PermissionKind::InputMonitoring => {
+            let hid_access = unsafe { IOHIDCheckAccess(IOHID_REQUEST_TYPE_LISTEN_EVENT) };
+
+            details.hid_access = Some(hid_access);
+
+            // Reference: https://developer.apple.com/documentation/iokit/iohidaccesstype
+            match hid_access {
+                0 => PermissionStatus::Granted,
+                1 => PermissionStatus::Denied,
+                _ => PermissionStatus::NotDetermined,
+            }
+        }
+        PermissionKind::Location => {
+            let authorization_status = location::authorization_status();
+
+            details.authorization_status = Some(authorization_status);
+
+            if !location::location_services_enabled() {
+                return (PermissionStatus::Restricted, details);
+            }
+
+            // Reference: https://developer.apple.com/documentation/corelocation/clauthorizationstatus
+            match authorization_status {
+                0 => PermissionStatus::NotDetermined,
+                1 => PermissionStatus::Restricted,
+                2 => PermissionStatus::Denied,
+                _ => PermissionStatus::Granted,
+            }
+        }
+        PermissionKind::Notifications => {
+            let Some(authorization_status) = notifications::authorization_status() else {
+                return (PermissionStatus::Unsupported, details);
+            };
+
+            details.authorization_status = Some(authorization_status as i32);
+
+            // Reference: https://developer.apple.com/documentation/usernotifications/unauthorizationstatus
+            match authorization_status {
+                0 => PermissionStatus::NotDetermined,
+                1 => PermissionStatus::Denied,
+                _ => PermissionStatus::Granted,
+            }
+        }
+    };
+
+    (status, details)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn probe<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    kind: PermissionKind,
+) -> (PermissionStatus, PermissionDetails) {
+    let _ = (app_handle, kind);
+
+    (PermissionStatus::Granted, PermissionDetails::default())
+}
+
+/// Query the status of `kind` for the calling process without a Tauri app, e.g. from a launchd agent or helper.
+///
+/// The home directory is read from `HOME`. Works without `NSApplication` and off the main thread, but:
+/// - Everything except full disk access is `Unsupported` without a window server, as in launchd daemons.
+/// - Notifications are `Unsupported` outside of an app bundle.
+/// - Grants apply to the responsible process, a helper spawned by the app shares its statuses, a launchd agent has its own.
+///
+/// Requests always need the app.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::{native_status, PermissionKind};
+///
+/// let status = native_status(PermissionKind::FullDiskAccess);
+/// println!("Status: {:?}", status); // Granted
+/// ```
+pub fn native_status(kind: PermissionKind) -> PermissionStatus {
+    #[cfg(target_os = "macos")]
+    {
+        let home_dir = std::env::var_os("HOME").map(PathBuf::from);
+
+        probe_in(home_dir.as_deref(), kind).0
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = kind;
+
+        PermissionStatus::Granted
+    }
+}
+
+/// Read the statuses another app is recorded with, identified by its bundle id, without running inside it.
+///
+/// The statuses come from the TCC databases, so the calling process needs full disk access to read them.
+/// Location and notifications are not recorded there and are always `Unsupported`.
+///
+/// # Example
+/// ```
+/// use tauri_plugin_macos_permissions::bundle_statuses;
+///
+/// for entry in bundle_statuses("com.example.app") {
+///     println!("{:?}: {:?}", entry.kind, entry.status); // Accessibility: Granted
+/// }
+/// ```
+pub fn bundle_statuses(bundle_id: &str) -> Vec<PermissionStatusEntry> {
+    PermissionKind::ALL
+        .into_iter()
+        .map(|kind| PermissionStatusEntry {
+            kind,
+            status: bundle_status(bundle_id, kind),
+        })
+        .collect()
+}
+
+fn bundle_status(bundle_id: &str, kind: PermissionKind) -> PermissionStatus {
+    #[cfg(target_os = "macos")]
+    return match kind {
+        PermissionKind::Location | PermissionKind::Notifications => PermissionStatus::Unsupported,
+        _ => {
+            let home_dir = std::env::var_os("HOME").map(PathBuf::from);
+
+            crate::tcc::client_status(home_dir.as_deref(), bundle_id, kind)
+        }
+    };
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (bundle_id, kind);
+
+        PermissionStatus::Unsupported
+    }
+}
+
+/// The `AVMediaType` of the capture devices gated by `kind`.
+#[cfg(target_os = "macos")]
+fn media_type(kind: PermissionKind) -> Option<&'static str> {
+    match kind {
+        PermissionKind::Microphone => Some(AV_MEDIA_TYPE_AUDIO),
+        PermissionKind::Camera => Some(AV_MEDIA_TYPE_VIDEO),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn granted_or_denied(granted: bool) -> PermissionStatus {
+    if granted {
+        PermissionStatus::Granted
+    } else {
+        PermissionStatus::Denied
+    }
+}
+
+/// Returns whether a configuration profile controls `kind`, `None` if it cannot be determined.
+pub(crate) fn managed(kind: PermissionKind) -> Option<bool> {
+    #[cfg(target_os = "macos")]
+    return crate::tcc::is_managed(kind);
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = kind;
+
+        Some(false)
+    }
+}
+
+/// Returns whether a capture device gated by `kind` is connected, `None` for other permissions.
+pub(crate) fn device_connected(kind: PermissionKind) -> Option<bool> {
+    #[cfg(target_os = "macos")]
+    return media_type(kind).map(has_capture_device);
+
+    #[cfg(not(target_os = "macos"))]
+    return matches!(kind, PermissionKind::Microphone | PermissionKind::Camera).then_some(true);
+}
+
+/// Returns whether global shortcuts can be registered, see `check_global_shortcut_readiness`.
+pub(crate) fn shortcut_readiness(event_tap: bool) -> ShortcutReadiness {
+    let blocker = {
+        #[cfg(target_os = "macos")]
+        let has_gui_session = has_gui_session();
+
+        #[cfg(not(target_os = "macos"))]
+        let has_gui_session = true;
+
+        if !has_gui_session {
+            Some(ShortcutBlocker::NoGuiSession)
+        } else if !event_tap {
+            None
+        } else if !check_accessibility_permission_blocking() {
+            Some(ShortcutBlocker::AccessibilityMissing)
+        } else if !check_input_monitoring_permission_blocking() {
+            Some(ShortcutBlocker::InputMonitoringMissing)
+        } else {
+            #[cfg(target_os = "macos")]
+            let can_create_event_tap = can_create_event_tap();
+
+            #[cfg(not(target_os = "macos"))]
+            let can_create_event_tap = true;
+
+            (!can_create_event_tap).then_some(ShortcutBlocker::EventTapUnavailable)
+        }
+    };
+
+    ShortcutReadiness {
+        ready: blocker.is_none(),
+        blocker,
+    }
+}
@@ -0,0 +1,27 @@
+use swift_rs::{swift, Int};
+
+use crate::models::Verification;
+
+swift!(fn macos_permissions_notification_authorization_status() -> Int);
+swift!(fn macos_permissions_capture_main_display() -> Int);
+
+/// Returns the raw `UNAuthorizationStatus` from the async settings API, `None` before macOS 12.
+pub fn notification_authorization_status() -> Option<isize> {
+    let status = unsafe { macos_permissions_notification_authorization_status() };
+
+    (status >= 0).then_some(status)
+}
+
+/// Capture the main display with ScreenCaptureKit, `None` before macOS 14.
+pub fn verify_screen_capture() -> Option<Verification> {
+    match unsafe { macos_permissions_capture_main_display() } {
+        0 => Some(Verification::granted()),
+        1 => Some(Verification::ineffective(
+            "The captured frame is black, relaunch the app to apply the permission",
+        )),
+        2 => Some(Verification::ineffective(
+            "No frame could be captured from the main display",
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,498 @@
+use std::path::{Path, PathBuf};
+#[cfg(not(feature = "app-store"))]
+use std::{
+    ffi::{c_char, c_void, CString},
+    process::Command,
+    ptr::{null, null_mut},
+    sync::OnceLock,
+};
+
+use objc2::{class, msg_send, rc::Retained, runtime::AnyObject};
+#[cfg(not(feature = "app-store"))]
+use objc2_foundation::NSArray;
+use objc2_foundation::{NSBundle, NSString};
+#[cfg(not(feature = "app-store"))]
+use tauri::{AppHandle, Manager, Runtime};
+
+#[cfg(not(feature = "app-store"))]
+use crate::manager::PermissionsManager;
+use crate::{
+    macos::catch_exception,
+    models::{PermissionKind, PermissionStatus, PromptHistory},
+};
+
+#[cfg(not(feature = "app-store"))]
+extern "C" {
+    fn dlopen(path: *const c_char, mode: i32) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+#[cfg(not(feature = "app-store"))]
+const RTLD_LAZY: i32 = 1;
+
+/// The private framework answering TCC queries, loaded at runtime since it cannot be linked against.
+#[cfg(not(feature = "app-store"))]
+const TCC_FRAMEWORK: &str = "/System/Library/PrivateFrameworks/TCC.framework/TCC";
+
+#[cfg(not(feature = "app-store"))]
+type TCCAccessPreflight =
+    unsafe extern "C" fn(service: *const c_void, options: *const c_void) -> i32;
+
+/// The TCC databases, the system one holds the services granted in Privacy & Security that are not per user.
+///
+/// Both are only readable with full disk access.
+#[cfg(not(feature = "app-store"))]
+const TCC_DATABASES: [&str; 2] = [
+    "Library/Application Support/com.apple.TCC/TCC.db",
+    "/Library/Application Support/com.apple.TCC/TCC.db",
+];
+
+/// `kFSEventStreamEventIdSinceNow`.
+#[cfg(not(feature = "app-store"))]
+const FS_EVENT_STREAM_EVENT_ID_SINCE_NOW: u64 = u64::MAX;
+
+/// `kFSEventStreamCreateFlagNoDefer`, the first change of a burst is delivered right away.
+#[cfg(not(feature = "app-store"))]
+const FS_EVENT_STREAM_CREATE_FLAG_NO_DEFER: u32 = 0x02;
+
+/// The permissions recorded in the TCC databases, location and notifications are kept elsewhere.
+#[cfg(not(feature = "app-store"))]
+const DATABASE_KINDS: [PermissionKind; 6] = [
+    PermissionKind::Accessibility,
+    PermissionKind::FullDiskAccess,
+    PermissionKind::ScreenRecording,
+    PermissionKind::Microphone,
+    PermissionKind::Camera,
+    PermissionKind::InputMonitoring,
+];
+
+#[cfg(not(feature = "app-store"))]
+#[repr(C)]
+struct FSEventStreamContext {
+    version: isize,
+    info: *mut c_void,
+    retain: *const c_void,
+    release: *const c_void,
+    copy_description: *const c_void,
+}
+
+#[cfg(not(feature = "app-store"))]
+type FSEventStreamCallback = extern "C" fn(
+    stream: *const c_void,
+    info: *mut c_void,
+    num_events: usize,
+    event_paths: *mut c_void,
+    event_flags: *const u32,
+    event_ids: *const u64,
+);
+
+#[cfg(not(feature = "app-store"))]
+#[link(name = "CoreServices", kind = "framework")]
+extern "C" {
+    fn FSEventStreamCreate(
+        allocator: *const c_void,
+        callback: FSEventStreamCallback,
+        context: *const FSEventStreamContext,
+        paths_to_watch: *const c_void,
+        since_when: u64,
+        latency: f64,
+        flags: u32,
+    ) -> *mut c_void;
+    fn FSEventStreamSetDispatchQueue(stream: *mut c_void, queue: *mut c_void);
+    fn FSEventStreamStart(stream: *mut c_void) -> bool;
+    fn FSEventStreamInvalidate(stream: *mut c_void);
+    fn FSEventStreamRelease(stream: *mut c_void);
+    fn dispatch_queue_create(label: *const c_char, attr: *const c_void) -> *mut c_void;
+}
+
+/// Called by [`database_changed`] whenever a TCC database is written, set by [`observe_databases`].
+#[cfg(not(feature = "app-store"))]
+static DATABASE_CHANGED: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
+
+#[cfg(not(feature = "app-store"))]
+extern "C" fn database_changed(
+    _stream: *const c_void,
+    _info: *mut c_void,
+    _num_events: usize,
+    _event_paths: *mut c_void,
+    _event_flags: *const u32,
+    _event_ids: *const u64,
+) {
+    if let Some(callback) = DATABASE_CHANGED.get() {
+        callback();
+    }
+}
+
+/// Refresh the permissions recorded in the TCC databases as soon as one of them is written, instead of waiting for the next poll.
+///
+/// FSEvents only reports changes of the databases with full disk access, without it nothing is observed.
+/// The stream runs for the lifetime of the app.
+#[cfg(not(feature = "app-store"))]
+pub fn observe_databases<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    home_dir: &Path,
+) -> Result<(), String> {
+    let handle = app_handle.clone();
+
+    let _ = DATABASE_CHANGED.set(Box::new(move || {
+        let app_handle = handle.clone();
+        let manager = app_handle.state::<PermissionsManager>();
+
+        for kind in DATABASE_KINDS {
+            if manager.config().permissions.contains(&kind) {
+                manager.push(&app_handle, kind);
+            }
+        }
+    }));
+
+    // The directories are watched, SQLite writes to journal files next to the databases.
+    let paths: Vec<_> = TCC_DATABASES
+        .iter()
+        .filter_map(|database| home_dir.join(database).parent().map(Path::to_path_buf))
+        .map(|directory| NSString::from_str(&directory.to_string_lossy()))
+        .collect();
+    let paths = NSArray::from_retained_slice(&paths);
+
+    let context = FSEventStreamContext {
+        version: 0,
+        info: null_mut(),
+        retain: null(),
+        release: null(),
+        copy_description: null(),
+    };
+
+    unsafe {
+        // NSArray is toll-free bridged to CFArray.
+        let stream = FSEventStreamCreate(
+            null(),
+            database_changed,
+            &context,
+            Retained::as_ptr(&paths).cast(),
+            FS_EVENT_STREAM_EVENT_ID_SINCE_NOW,
+            0.05,
+            FS_EVENT_STREAM_CREATE_FLAG_NO_DEFER,
+        );
+
+        if stream.is_null() {
+            return Err("Failed to create the TCC database event stream".into());
+        }
+
+        let label = CString::new("tauri-plugin-macos-permissions.tcc").unwrap_or_default();
+        let queue = dispatch_queue_create(label.as_ptr(), null());
+
+        FSEventStreamSetDispatchQueue(stream, queue);
+
+        if !FSEventStreamStart(stream) {
+            FSEventStreamInvalidate(stream);
+            FSEventStreamRelease(stream);
+
+            return Err("Failed to start the TCC database event stream".into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Grants and denials forced by configuration profiles (PPPC payloads), keyed by client then service.
+const MDM_OVERRIDES: &str = "/Library/Application Support/com.apple.TCC/MDMOverrides.plist";
+
+/// The TCC service backing `kind`.
+pub fn service(kind: PermissionKind) -> &'static str {
+    match kind {
+        PermissionKind::Accessibility => "kTCCServiceAccessibility",
+        PermissionKind::FullDiskAccess => "kTCCServiceSystemPolicyAllFiles",
+        PermissionKind::ScreenRecording => "kTCCServiceScreenCapture",
+        PermissionKind::Microphone => "kTCCServiceMicrophone",
+        PermissionKind::Camera => "kTCCServiceCamera",
+        PermissionKind::InputMonitoring => "kTCCServiceListenEvent",
+        PermissionKind::Location => "kTCCServiceLocation",
+        PermissionKind::Notifications => "kTCCServiceUserNotification",
+    }
+}
+
+/// The client TCC records the app under, its bundle identifier or the executable path outside of a bundle.
+pub fn client() -> Option<String> {
+    if let Some(identifier) = NSBundle::mainBundle().bundleIdentifier() {
+        return Some(identifier.to_string());
+    }
+
+    std::env::current_exe()
+        .ok()
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+/// Returns whether the app has a record for `kind` in a TCC database, `None` if no database is readable.
+pub fn has_record(home_dir: Option<PathBuf>, kind: PermissionKind) -> Option<bool> {
+    let client = client()?.replace('\'', "''");
+
+    let query = format!(
+        "SELECT COUNT(*) FROM access WHERE service = '{}' AND client = '{client}'",
+        service(kind)
+    );
+
+    let counts = query_databases(home_dir.as_deref(), &query)?;
+
+    Some(
+        counts
+            .iter()
+            .any(|count| count.trim().parse::<u32>().is_ok_and(|count| count > 0)),
+    )
+}
+
+/// Returns the status `client` is recorded with for `kind`, read from the `auth_value` of the TCC databases.
+///
+/// `Unsupported` if no database is readable, `NotDetermined` if the client has no record.
+pub fn client_status(
+    home_dir: Option<&Path>,
+    client: &str,
+    kind: PermissionKind,
+) -> PermissionStatus {
+    let query = format!(
+        "SELECT auth_value FROM access WHERE service = '{}' AND client = '{}'",
+        service(kind),
+        client.replace('\'', "''")
+    );
+
+    let Some(outputs) = query_databases(home_dir, &query) else {
+        return PermissionStatus::Unsupported;
+    };
+
+    // `auth_value` is 0 when denied, 2 when allowed and 3 when limited.
+    let auth_value = outputs
+        .iter()
+        .find_map(|output| output.lines().next()?.trim().parse::<i32>().ok());
+
+    match auth_value {
+        None => PermissionStatus::NotDetermined,
+        Some(0) => PermissionStatus::Denied,
+        Some(2 | 3) => PermissionStatus::Granted,
+        Some(_) => PermissionStatus::Unsupported,
+    }
+}
+
+/// Returns when the TCC databases last recorded a decision of the app for `kind`.
+///
+/// `None` if no database is readable or the app has no record.
+pub fn prompt_history(home_dir: Option<&Path>, kind: PermissionKind) -> Option<PromptHistory> {
+    let client = client()?.replace('\'', "''");
+    let query = |columns: &str| {
+        format!(
+            "SELECT {columns} FROM access WHERE service = '{}' AND client = '{client}'",
+            service(kind)
+        )
+    };
+
+    // `last_reminded` only exists since macOS 12.
+    let outputs = query_databases(
+        home_dir,
+        &query("last_modified, auth_reason, last_reminded"),
+    )
+    .or_else(|| query_databases(home_dir, &query("last_modified, auth_reason")))?;
+
+    // Timestamps are seconds since the Unix epoch, `last_reminded` is 0 when never reminded.
+    let records = outputs
+        .iter()
+        .flat_map(|output| output.lines())
+        .map(|line| {
+            let mut values = line
+                .split('|')
+                .map(|value| value.trim().parse::<u64>().unwrap_or_default());
+
+            (
+                values.next().unwrap_or_default() * 1000,
+                values.next().unwrap_or_default(),
+                values.next().map(|last_reminded| last_reminded * 1000),
+            )
+        });
+
+    // Both databases may hold a record, the latest one wins.
+    let (last_modified, auth_reason, last_reminded) =
+        records.max_by_key(|(last_modified, ..)| *last_modified)?;
+
+    Some(PromptHistory {
+        kind,
+        // `auth_reason` is 2 when the decision was the answer to the prompt, 3 when it was changed in System Settings.
+        last_prompted: (auth_reason == 2).then_some(last_modified),
+        last_modified,
+        last_reminded: last_reminded.filter(|last_reminded| *last_reminded > 0),
+    })
+}
+
+/// Run `query` against each readable TCC database, returning their outputs or `None` if no database is readable.
+#[cfg(not(feature = "app-store"))]
+fn query_databases(home_dir: Option<&Path>, query: &str) -> Option<Vec<String>> {
+    let mut outputs = Vec::new();
+
+    for database in TCC_DATABASES {
+        let path = match home_dir {
+            Some(home_dir) if !database.starts_with('/') => home_dir.join(database),
+            None if !database.starts_with('/') => continue,
+            _ => PathBuf::from(database),
+        };
+
+        let Ok(output) = Command::new("sqlite3")
+            .arg("-readonly")
+            .arg(&path)
+            .arg(query)
+            .output()
+        else {
+            continue;
+        };
+
+        if !output.status.success() {
+            continue;
+        }
+
+        outputs.push(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+
+    (!outputs.is_empty()).then_some(outputs)
+}
+
+/// The TCC databases are out of reach of sandboxed apps, and `sqlite3` cannot be spawned.
+#[cfg(feature = "app-store")]
+fn query_databases(home_dir: Option<&Path>, query: &str) -> Option<Vec<String>> {
+    let _ = (home_dir, query);
+
+    None
+}
+
+/// Returns whether a configuration profile controls `kind` for the app.
+///
+/// `None` if the overrides exist but cannot be read, `Some(false)` on machines without any override.
+pub fn is_managed(kind: PermissionKind) -> Option<bool> {
+    if !Path::new(MDM_OVERRIDES).exists() {
+        return Some(false);
+    }
+
+    let client = NSString::from_str(&client()?);
+    let service = NSString::from_str(service(kind));
+    let path = NSString::from_str(MDM_OVERRIDES);
+
+    catch_exception(|| unsafe {
+        let overrides: Option<Retained<AnyObject>> =
+            msg_send![class!(NSDictionary), dictionaryWithContentsOfFile: &*path];
+        let overrides = overrides?;
+
+        let services: Option<Retained<AnyObject>> = msg_send![&*overrides, objectForKey: &*client];
+
+        let Some(services) = services else {
+            return Some(false);
+        };
+
+        let service: Option<Retained<AnyObject>> = msg_send![&*services, objectForKey: &*service];
+
+        Some(service.is_some())
+    })
+    .ok()
+    .flatten()
+}
+
+/// Returns the raw `TCCAccessPreflight` result of `service`, `None` if the private framework is unavailable.
+#[cfg(not(feature = "app-store"))]
+pub fn preflight(service: &str) -> Option<i32> {
+    let path = CString::new(TCC_FRAMEWORK).ok()?;
+    let symbol = CString::new("TCCAccessPreflight").ok()?;
+
+    unsafe {
+        let handle = dlopen(path.as_ptr(), RTLD_LAZY);
+
+        if handle.is_null() {
+            return None;
+        }
+
+        let function = dlsym(handle, symbol.as_ptr());
+
+        if function.is_null() {
+            return None;
+        }
+
+        let function: TCCAccessPreflight = std::mem::transmute(function);
+        let service = NSString::from_str(service);
+
+        Some(function(Retained::as_ptr(&service).cast(), null()))
+    }
+}
+
+/// App Review rejects apps loading private frameworks.
+#[cfg(feature = "app-store")]
+pub fn preflight(service: &str) -> Option<i32> {
+    let _ = service;
+
+    None
+}
+
+/// The status of a raw TCC service, e.g. `kTCCServiceCalendar`.
+pub fn service_status(service: &str) -> PermissionStatus {
+    match preflight(service) {
+        Some(0) => PermissionStatus::Granted,
+        Some(1) => PermissionStatus::Denied,
+        Some(2) => PermissionStatus::NotDetermined,
+        _ => PermissionStatus::Unsupported,
+    }
+}
+
+/// The Privacy & Security pane of a raw TCC service, e.g. `Privacy_Calendars` for `kTCCServiceCalendar`.
+///
+/// `None` unless the service is named in letters only, so nothing else ends up in the settings URL.
+pub fn settings_anchor(service: &str) -> Option<String> {
+    let name = service
+        .strip_prefix("kTCCService")
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic()))?;
+
+    // Most panes are named after the service, these are not.
+    let pane = match name {
+        "SystemPolicyAllFiles" => "AllFiles",
+        "SystemPolicyDesktopFolder"
+        | "SystemPolicyDocumentsFolder"
+        | "SystemPolicyDownloadsFolder"
+        | "SystemPolicyNetworkVolumes"
+        | "SystemPolicyRemovableVolumes" => "FilesAndFolders",
+        "AppleEvents" => "Automation",
+        "AddressBook" => "Contacts",
+        "Calendar" => "Calendars",
+        "PostEvent" => "Accessibility",
+        "DeveloperTool" => "DevTools",
+        "MediaLibrary" => "Media",
+        "BluetoothAlways" => "Bluetooth",
+        "FocusStatus" => "Focus",
+        name => name,
+    };
+
+    Some(format!("Privacy_{pane}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_anchor_names_the_pane_of_a_service() {
+        assert_eq!(
+            settings_anchor("kTCCServiceCamera").as_deref(),
+            Some("Privacy_Camera")
+        );
+        assert_eq!(
+            settings_anchor("kTCCServiceCalendar").as_deref(),
+            Some("Privacy_Calendars")
+        );
+        assert_eq!(
+            settings_anchor("kTCCServiceSystemPolicyDownloadsFolder").as_deref(),
+            Some("Privacy_FilesAndFolders")
+        );
+    }
+
+    #[test]
+    fn settings_anchor_rejects_anything_but_letters() {
+        for service in [
+            "Camera",
+            "kTCCService",
+            "kTCCServiceCamera&foo",
+            "kTCCServiceCamera?x-apple",
+            "kTCCService Camera",
+            "kTCCServiceCamera/../",
+        ] {
+            assert_eq!(settings_anchor(service), None, "{service}");
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use futures_util::StreamExt;
+use tauri::{
+    menu::{MenuItem, Submenu},
+    Manager, Runtime,
+};
+
+use crate::{
+    manager::PermissionsManager,
+    models::{PermissionKind, PermissionStatus},
+};
+
+const MENU_ID_PREFIX: &str = "macos-permissions-status:";
+
+/// Build a tray submenu listing `kinds` with their status, kept up to date while the app runs.
+///
+/// Clicking a permission that is not granted opens its System Settings pane.
+///
+/// # Arguments
+/// - `kinds`: The permissions to list, defaults to the `permissions` config.
+///
+/// # Returns
+/// - `Submenu`: The submenu to append to the tray menu.
+///
+/// # Example
+/// ```
+/// use tauri::menu::Menu;
+/// use tauri_plugin_macos_permissions::status_menu;
+///
+/// let menu = Menu::with_items(app, &[&status_menu(app, None)?])?;
+/// ```
+pub fn status_menu<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    kinds: Option<Vec<PermissionKind>>,
+) -> tauri::Result<Submenu<R>> {
+    let app_handle = manager.app_handle().clone();
+    let state = app_handle.state::<PermissionsManager>();
+
+    let kinds = kinds.unwrap_or_else(|| state.config().permissions.clone());
+    let summary = state.summary(&app_handle, &kinds);
+
+    let submenu = Submenu::new(&app_handle, title(summary.ok), true)?;
+    let mut ids = HashMap::new();
+
+    for entry in summary.permissions {
+        let id = format!("{MENU_ID_PREFIX}{}", entry.kind.label());
+
+        let item = MenuItem::with_id(
+            &app_handle,
+            id.clone(),
+            text(entry.kind, entry.status),
+            !entry.status.is_granted(),
+            None::<&str>,
+        )?;
+
+        submenu.append(&item)?;
+        ids.insert(id, entry.kind);
+
+        let changes = state.subscribe(entry.kind);
+        let app_handle = app_handle.clone();
+        let submenu = submenu.clone();
+        let kinds = kinds.clone();
+
+        tauri::async_runtime::spawn(async move {
+            let mut changes = std::pin::pin!(changes);
+
+            while let Some(change) = changes.next().await {
+                let manager = app_handle.state::<PermissionsManager>();
+
+                let ok = kinds.iter().all(|&kind| {
+                    manager
+                        .cached_status(kind)
+                        .is_some_and(PermissionStatus::is_granted)
+                });
+
+                let _ = item.set_text(text(change.kind, change.status));
+                let _ = item.set_enabled(!change.status.is_granted());
+                let _ = submenu.set_text(title(ok));
+            }
+        });
+    }
+
+    app_handle.on_menu_event(move |app_handle, event| {
+        if let Some(kind) = ids.get(event.id().as_ref()) {
+            let _ = app_handle
+                .state::<PermissionsManager>()
+                .open_settings(*kind);
+        }
+    });
+
+    Ok(submenu)
+}
+
+fn title(ok: bool) -> &'static str {
+    if ok {
+        "Permissions"
+    } else {
+        "⚠️ Permissions Needed"
+    }
+}
+
+fn text(kind: PermissionKind, status: PermissionStatus) -> String {
+    if status.is_granted() {
+        format!("✓ {}", kind.label())
+    } else {
+        format!("⚠️ {} needed", kind.label())
+    }
+}
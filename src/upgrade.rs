@@ -0,0 +1,118 @@
+use std::{fs, sync::Mutex};
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::{manager::PermissionsManager, models::OsUpgraded};
+
+/// Emitted once the permissions were re-verified on the first launch after a macOS major upgrade, with an [`OsUpgraded`] payload.
+pub const OS_UPGRADED_EVENT: &str = "macos-permissions://os-upgraded";
+
+const FILE_NAME: &str = "macos-permissions-os-version.json";
+
+/// The macOS upgrade detected on this launch, upgrades regularly reset or re-scope grants.
+///
+/// The version of the last launch is persisted in the app data directory.
+#[derive(Default)]
+pub struct OsUpgrade {
+    /// The previous and the running version.
+    versions: Option<(String, String)>,
+    upgraded: Mutex<Option<OsUpgraded>>,
+}
+
+impl OsUpgrade {
+    /// Compare the running macOS version with the one of the last launch, and remember it for the next one.
+    pub(crate) fn detect<R: Runtime>(app_handle: &AppHandle<R>) -> Self {
+        let (Some(version), Ok(dir)) = (os_version(), app_handle.path().app_data_dir()) else {
+            return Self::default();
+        };
+
+        let path = dir.join(FILE_NAME);
+
+        let previous_version: Option<String> = fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+
+        if previous_version.as_ref() != Some(&version) {
+            let _ = fs::create_dir_all(&dir);
+
+            if let Ok(bytes) = serde_json::to_vec(&version) {
+                let _ = fs::write(&path, bytes);
+            }
+        }
+
+        // The first launch ever is no upgrade, neither is a minor update.
+        Self {
+            versions: previous_version
+                .filter(|previous| major(previous) != major(&version))
+                .map(|previous| (previous, version)),
+            upgraded: Mutex::new(None),
+        }
+    }
+
+    /// The upgrade detected on this launch, once the permissions were re-verified.
+    pub fn get(&self) -> Option<OsUpgraded> {
+        self.upgraded.lock().unwrap().clone()
+    }
+
+    /// Re-verify the permissions of the `permissions` config after an upgrade, then emit [`OS_UPGRADED_EVENT`].
+    pub(crate) fn spawn_verification<R: Runtime>(app_handle: AppHandle<R>) {
+        let Some((previous_version, version)) = app_handle.state::<OsUpgrade>().versions.clone()
+        else {
+            return;
+        };
+
+        tauri::async_runtime::spawn(async move {
+            let manager = app_handle.state::<PermissionsManager>();
+
+            let upgraded = OsUpgraded {
+                previous_version,
+                version,
+                summary: manager.summary(&app_handle, &manager.config().permissions),
+            };
+
+            *app_handle.state::<OsUpgrade>().upgraded.lock().unwrap() = Some(upgraded.clone());
+
+            manager.emit(&app_handle, OS_UPGRADED_EVENT, upgraded);
+        });
+    }
+}
+
+/// The running macOS version, e.g. `15.1`.
+fn os_version() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    return crate::macos::os_version();
+
+    #[cfg(not(target_os = "macos"))]
+    None
+}
+
+/// The major part of a macOS version, `10.15` before macOS 11 and `15` after.
+fn major(version: &str) -> &str {
+    let mut parts = version.match_indices('.').map(|(index, _)| index);
+
+    let end = if version.starts_with("10.") {
+        parts.nth(1)
+    } else {
+        parts.next()
+    };
+
+    &version[..end.unwrap_or(version.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn major_keeps_the_minor_version_before_macos_11() {
+        assert_eq!(major("10.15.7"), "10.15");
+        assert_eq!(major("10.14"), "10.14");
+    }
+
+    #[test]
+    fn major_drops_the_minor_version_since_macos_11() {
+        assert_eq!(major("15.1"), "15");
+        assert_eq!(major("14.6.1"), "14");
+        assert_eq!(major("26"), "26");
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    ffi::c_void,
+    ptr::null_mut,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+
+use block2::RcBlock;
+use objc2::{
+    class, msg_send,
+    rc::Retained,
+    runtime::{AnyObject, Bool},
+};
+use objc2_foundation::NSString;
+
+use crate::{
+    macos::{
+        catch_exception, has_capture_device, on_screen_windows, CFRelease, AV_MEDIA_TYPE_AUDIO,
+    },
+    models::Verification,
+};
+
+#[link(name = "AVFoundation", kind = "framework")]
+extern "C" {}
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXUIElementCreateSystemWide() -> *const c_void;
+    fn AXUIElementCopyAttributeValue(
+        element: *const c_void,
+        attribute: *const c_void,
+        value: *mut *const c_void,
+    ) -> i32;
+}
+
+/// `kAXErrorSuccess`.
+const AX_ERROR_SUCCESS: i32 = 0;
+
+/// `kAXErrorAttributeUnsupported`.
+const AX_ERROR_ATTRIBUTE_UNSUPPORTED: i32 = -25205;
+
+/// `kAXErrorAPIDisabled`, returned when the process is not trusted.
+const AX_ERROR_API_DISABLED: i32 = -25211;
+
+/// `kAXErrorNoValue`.
+const AX_ERROR_NO_VALUE: i32 = -25212;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGMainDisplayID() -> u32;
+    fn CGDisplayCreateImage(display: u32) -> *const c_void;
+    fn CGImageGetDataProvider(image: *const c_void) -> *const c_void;
+    fn CGDataProviderCopyData(provider: *const c_void) -> *const c_void;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFDataGetBytePtr(data: *const c_void) -> *const u8;
+    fn CFDataGetLength(data: *const c_void) -> isize;
+}
+
+/// Capture audio from the default input for `duration` and check that non-silent frames arrive.
+pub fn verify_microphone(duration: Duration) -> Verification {
+    if !has_capture_device(AV_MEDIA_TYPE_AUDIO) {
+        return Verification::ineffective("No microphone is connected");
+    }
+
+    let frames = Arc::new(AtomicU64::new(0));
+    let audible = Arc::new(AtomicBool::new(false));
+
+    let tap = RcBlock::new({
+        let frames = frames.clone();
+        let audible = audible.clone();
+
+        move |buffer: *mut AnyObject, _when: *mut AnyObject| unsafe {
+            let length: u32 = msg_send![buffer, frameLength];
+            let channels: *const *const f32 = msg_send![buffer, floatChannelData];
+
+            frames.fetch_add(length as u64, Ordering::Relaxed);
+
+            if channels.is_null() || length == 0 {
+                return;
+            }
+
+            let samples = std::slice::from_raw_parts(*channels, length as usize);
+
+            if samples.iter().any(|sample| *sample != 0.0) {
+                audible.store(true, Ordering::Relaxed);
+            }
+        }
+    });
+
+    // `installTapOnBus` raises when the input format is unusable, e.g. without an input device.
+    let failure = catch_exception(|| unsafe {
+        let engine: Retained<AnyObject> = msg_send![class!(AVAudioEngine), new];
+        let input: Retained<AnyObject> = msg_send![&*engine, inputNode];
+        let format: Retained<AnyObject> = msg_send![&*input, outputFormatForBus: 0usize];
+
+        let _: () = msg_send![
+            &*input,
+            installTapOnBus: 0usize,
+            bufferSize: 1024u32,
+            format: &*format,
+            block: &*tap
+        ];
+
+        let mut error: *mut AnyObject = null_mut();
+        let started: Bool = msg_send![&*engine, startAndReturnError: &mut error];
+
+        if started.as_bool() {
+            sleep(duration);
+
+            let _: () = msg_send![&*engine, stop];
+        }
+
+        let _: () = msg_send![&*input, removeTapOnBus: 0usize];
+
+        if !started.as_bool() {
+            let reason = if error.is_null() {
+                "The audio engine could not be started".to_string()
+            } else {
+                let description: Retained<NSString> = msg_send![error, localizedDescription];
+
+                description.to_string()
+            };
+
+            return Some(Verification::ineffective(reason));
+        }
+
+        None
+    });
+
+    match failure {
+        Ok(Some(verification)) => return verification,
+        Err(error) => return Verification::ineffective(error),
+        Ok(None) => {}
+    }
+
+    if frames.load(Ordering::Relaxed) == 0 {
+        return Verification::ineffective("No audio frames were captured");
+    }
+
+    // Even a quiet room has a noise floor, digital silence means macOS is muting the input.
+    if !audible.load(Ordering::Relaxed) {
+        return Verification::ineffective(
+            "Only silence was captured, the permission may need to be reset with `tccutil reset Microphone`",
+        );
+    }
+
+    Verification::granted()
+}
+
+/// Capture a frame of the main display and read the window titles of other apps.
+///
+/// `CGPreflightScreenCaptureAccess` reports a grant right away, while capture keeps returning
+/// black frames and hiding window titles until the app is relaunched.
+pub fn verify_screen_recording() -> Verification {
+    let pid = std::process::id() as i32;
+
+    let windows: Vec<_> = on_screen_windows()
+        .into_iter()
+        .filter(|window| window.owner_pid != pid)
+        .collect();
+
+    if !windows.is_empty() && windows.iter().all(|window| window.name.is_none()) {
+        return Verification::ineffective(
+            "Window titles of other apps are not readable, relaunch the app to apply the permission",
+        );
+    }
+
+    // CGDisplayCreateImage is obsoleted in macOS 15, ScreenCaptureKit replaces it.
+    #[cfg(feature = "swift")]
+    if let Some(verification) = crate::swift::verify_screen_capture() {
+        return verification;
+    }
+
+    unsafe {
+        let image = CGDisplayCreateImage(CGMainDisplayID());
+
+        if image.is_null() {
+            return Verification::ineffective("No frame could be captured from the main display");
+        }
+
+        let data = CGDataProviderCopyData(CGImageGetDataProvider(image));
+
+        CFRelease(image);
+
+        if data.is_null() {
+            return Verification::ineffective("The captured frame has no pixel data");
+        }
+
+        let bytes =
+            std::slice::from_raw_parts(CFDataGetBytePtr(data), CFDataGetLength(data) as usize);
+
+        // Sample the 32-bit pixels, ignoring the alpha channel.
+        let black = bytes
+            .chunks_exact(4)
+            .step_by(97)
+            .all(|pixel| pixel[..3].iter().all(|channel| *channel == 0));
+
+        CFRelease(data);
+
+        if black {
+            return Verification::ineffective(
+                "The captured frame is black, relaunch the app to apply the permission",
+            );
+        }
+    }
+
+    Verification::granted()
+}
+
+/// Read the title of the focused app through the accessibility API.
+///
+/// An app can be listed as trusted in System Settings while AX calls still fail, e.g. after it was
+/// re-signed or moved, the query catches that.
+pub fn verify_accessibility() -> Verification {
+    let copy_attribute = |element: *const c_void, attribute: &str| unsafe {
+        let attribute = NSString::from_str(attribute);
+        let mut value: *const c_void = null_mut();
+
+        let error =
+            AXUIElementCopyAttributeValue(element, Retained::as_ptr(&attribute).cast(), &mut value);
+
+        (error, value)
+    };
+
+    unsafe {
+        let system_wide = AXUIElementCreateSystemWide();
+        let (mut error, application) = copy_attribute(system_wide, "AXFocusedApplication");
+
+        CFRelease(system_wide);
+
+        if error == AX_ERROR_SUCCESS && !application.is_null() {
+            let (title_error, title) = copy_attribute(application, "AXTitle");
+
+            if !title.is_null() {
+                CFRelease(title);
+            }
+
+            CFRelease(application);
+
+            error = title_error;
+        }
+
+        match error {
+            AX_ERROR_SUCCESS | AX_ERROR_NO_VALUE | AX_ERROR_ATTRIBUTE_UNSUPPORTED => {
+                Verification::granted()
+            }
+            AX_ERROR_API_DISABLED => Verification::ineffective(
+                "The app is listed as trusted but accessibility calls are rejected, remove it from the Accessibility list and add it again",
+            ),
+            error => Verification::ineffective(format!(
+                "The accessibility query failed with AXError {error}"
+            )),
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    future::Future,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use futures_util::future::{self, Either};
+use tauri::{async_runtime::JoinHandle, ipc::Channel, AppHandle, Manager, Runtime};
+use tokio::sync::oneshot;
+
+use crate::{
+    error::Error,
+    manager::PermissionsManager,
+    models::{
+        PermissionChanged, PermissionKind, PermissionStatusEntry, PermissionUpdate,
+        PermissionsChanged, StatusChange,
+    },
+    status::shortcut_readiness,
+};
+
+/// Emitted when a watched permission changes.
+pub const PERMISSION_CHANGED_EVENT: &str = "macos-permissions://permission-changed";
+
+/// Emitted instead of [`PERMISSION_CHANGED_EVENT`] when watchers detect several changes within the `coalesce_window` config.
+pub const PERMISSIONS_CHANGED_EVENT: &str = "macos-permissions://permissions-changed";
+
+/// Emitted once global shortcuts can be registered, with the watcher id as payload.
+pub const GLOBAL_SHORTCUT_READY_EVENT: &str = "macos-permissions://global-shortcut-ready";
+
+/// A change waiting for the `coalesce_window` config to pass, with the targets of its watcher.
+type PendingChange = (Vec<String>, PermissionChanged, oneshot::Sender<bool>);
+
+/// Running permission watchers, owned by the [`PermissionsManager`].
+#[derive(Default)]
+pub struct Watchers {
+    next_id: AtomicU32,
+    next_wait: AtomicU64,
+    handles: Arc<Mutex<HashMap<u32, JoinHandle<()>>>>,
+    pending: Mutex<Vec<PendingChange>>,
+    /// The sender of each wait, with a token telling it apart from a later wait reusing the id.
+    cancellations: Mutex<HashMap<u32, (u64, oneshot::Sender<()>)>>,
+}
+
+impl Watchers {
+    /// Start polling `kind` every `interval`, emitting an event to `targets` whenever it changes.
+    ///
+    /// `targets` are window or webview labels, the `event_targets` config is used when `None`.
+    ///
+    /// Changes reported by the framework itself, like location through its delegate, are emitted right away.
+    pub fn watch<R: Runtime>(
+        &self,
+        app_handle: AppHandle<R>,
+        kind: PermissionKind,
+        interval: Duration,
+        targets: Option<Vec<String>>,
+    ) -> u32 {
+        self.spawn(|id| async move {
+            let manager = app_handle.state::<PermissionsManager>();
+            let targets = targets.unwrap_or_else(|| manager.config().event_targets.clone());
+            let mut authorized = kind.check(&app_handle).await;
+
+            loop {
+                manager.tick(&[kind], interval).await;
+
+                let current = kind.check(&app_handle).await;
+
+                if current == authorized {
+                    continue;
+                }
+
+                let change = PermissionChanged {
+                    id,
+                    kind,
+                    authorized: current,
+                };
+
+                if manager
+                    .watchers()
+                    .report(&app_handle, targets.clone(), change)
+                    .await
+                {
+                    authorized = current;
+                }
+            }
+        })
+    }
+
+    /// Emit a change detected by [`watch`](Self::watch), or with the `coalesce_window` config, collect it with the
+    /// changes other watchers detect until the window passed and emit them as one [`PERMISSIONS_CHANGED_EVENT`].
+    ///
+    /// Returns `false` if the permission was toggled back within the window, so there was nothing to report.
+    async fn report<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        targets: Vec<String>,
+        change: PermissionChanged,
+    ) -> bool {
+        let manager = app_handle.state::<PermissionsManager>();
+
+        let Some(window) = manager.config().coalesce_window() else {
+            manager.refresh(app_handle, change.kind);
+            manager.emit_to(app_handle, &targets, PERMISSION_CHANGED_EVENT, change);
+
+            return true;
+        };
+
+        let (sender, reported) = oneshot::channel();
+
+        let first = {
+            let mut pending = self.pending.lock().unwrap();
+
+            pending.push((targets, change, sender));
+            pending.len() == 1
+        };
+
+        // The first change of a burst flushes it, in its own task so unwatching its watcher does not lose the others.
+        if first {
+            let app_handle = app_handle.clone();
+
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(window).await;
+
+                let manager = app_handle.state::<PermissionsManager>();
+                let pending = std::mem::take(&mut *manager.watchers().pending.lock().unwrap());
+                let mut batches: Vec<(Vec<String>, Vec<PermissionChanged>)> = Vec::new();
+
+                for (targets, change, sender) in pending {
+                    // Toggled back within the window, there is nothing to report.
+                    let changed = change.kind.check(&app_handle).await == change.authorized;
+
+                    let _ = sender.send(changed);
+
+                    if !changed {
+                        continue;
+                    }
+
+                    manager.refresh(&app_handle, change.kind);
+
+                    match batches.iter_mut().find(|(batch, _)| *batch == targets) {
+                        Some((_, changes)) => changes.push(change),
+                        None => batches.push((targets, vec![change])),
+                    }
+                }
+
+                for (targets, mut changes) in batches {
+                    match changes.len() {
+                        1 => manager.emit_to(
+                            &app_handle,
+                            &targets,
+                            PERMISSION_CHANGED_EVENT,
+                            changes.remove(0),
+                        ),
+                        _ => manager.emit_to(
+                            &app_handle,
+                            &targets,
+                            PERMISSIONS_CHANGED_EVENT,
+                            PermissionsChanged { changes },
+                        ),
+                    }
+                }
+            });
+        }
+
+        reported.await.unwrap_or(false)
+    }
+
+    /// Send the status of `kinds` over `channel`, then every change detected while polling every `interval`.
+    ///
+    /// With the `coalesce_window` config, changes detected together are sent as one [`PermissionUpdate::Batch`].
+    ///
+    /// Stops on its own once the channel is closed, e.g. when the webview is reloaded.
+    pub fn subscribe<R: Runtime>(
+        &self,
+        app_handle: AppHandle<R>,
+        channel: Channel<PermissionUpdate>,
+        kinds: Vec<PermissionKind>,
+        interval: Duration,
+    ) -> u32 {
+        self.spawn(|_| async move {
+            let manager = app_handle.state::<PermissionsManager>();
+
+            let mut statuses: Vec<_> = kinds
+                .into_iter()
+                .map(|kind| PermissionStatusEntry {
+                    kind,
+                    status: manager.refresh(&app_handle, kind),
+                })
+                .collect();
+
+            let snapshot = PermissionUpdate::Snapshot {
+                statuses: statuses.clone(),
+            };
+
+            if channel.send(snapshot).is_err() {
+                return;
+            }
+
+            let kinds: Vec<_> = statuses.iter().map(|entry| entry.kind).collect();
+            let window = manager.config().coalesce_window();
+
+            loop {
+                manager.tick(&kinds, interval).await;
+
+                // Wait for the rest of a burst once something changed, e.g. several switches toggled in System Settings.
+                if let Some(window) = window {
+                    let changed = statuses
+                        .iter()
+                        .any(|entry| manager.refresh(&app_handle, entry.kind) != entry.status);
+
+                    if !changed {
+                        continue;
+                    }
+
+                    tokio::time::sleep(window).await;
+                }
+
+                let mut changes = Vec::new();
+
+                for entry in &mut statuses {
+                    let status = manager.refresh(&app_handle, entry.kind);
+
+                    if status != entry.status {
+                        changes.push(StatusChange {
+                            kind: entry.kind,
+                            status,
+                            previous: entry.status,
+                        });
+
+                        entry.status = status;
+                    }
+                }
+
+                let updates = if changes.len() > 1 && window.is_some() {
+                    vec![PermissionUpdate::Batch { changes }]
+                } else {
+                    changes.into_iter().map(PermissionUpdate::Changed).collect()
+                };
+
+                for update in updates {
+                    if channel.send(update).is_err() {
+                        return;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Poll every `interval` until global shortcuts can be registered, then emit an event and stop.
+    pub fn watch_global_shortcut_readiness<R: Runtime>(
+        &self,
+        app_handle: AppHandle<R>,
+        event_tap: bool,
+        interval: Duration,
+    ) -> u32 {
+        self.spawn(|id| async move {
+            while !shortcut_readiness(event_tap).ready {
+                tokio::time::sleep(interval).await;
+            }
+
+            app_handle.state::<PermissionsManager>().emit(
+                &app_handle,
+                GLOBAL_SHORTCUT_READY_EVENT,
+                id,
+            );
+        })
+    }
+
+    /// Run `task` in the background until it completes or is stopped with [`unwatch`](Self::unwatch).
+    pub(crate) fn spawn<F>(&self, task: impl FnOnce(u32) -> F) -> u32
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let task = task(id);
+        let handles = self.handles.clone();
+
+        // Held until the handle is inserted, so a task completing right away still finds it to remove.
+        let mut running = self.handles.lock().unwrap();
+
+        running.insert(
+            id,
+            tauri::async_runtime::spawn(async move {
+                task.await;
+
+                handles.lock().unwrap().remove(&id);
+            }),
+        );
+
+        id
+    }
+
+    /// Stop the watcher with the given id, returning `false` if it does not exist.
+    pub fn unwatch(&self, id: u32) -> bool {
+        match self.handles.lock().unwrap().remove(&id) {
+            Some(handle) => {
+                handle.abort();
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Run `future` until it completes, or return `None` once [`cancel`](Self::cancel) is called with `id`.
+    ///
+    /// The id is picked by the caller, so it is known before the wait starts. Fails without running `future`
+    /// if another wait is using the id.
+    pub async fn cancellable<T>(
+        &self,
+        id: u32,
+        future: impl Future<Output = T>,
+    ) -> Result<Option<T>, Error> {
+        let (sender, cancelled) = oneshot::channel();
+        let token = self.next_wait.fetch_add(1, Ordering::Relaxed);
+
+        match self.cancellations.lock().unwrap().entry(id) {
+            Entry::Occupied(_) => return Err(Error::CancelIdInUse(id)),
+            Entry::Vacant(entry) => {
+                entry.insert((token, sender));
+            }
+        }
+
+        // Also frees the id when the returned future is dropped before `future` completes.
+        let _registration = CancelRegistration {
+            cancellations: &self.cancellations,
+            id,
+            token,
+        };
+
+        let output = match future::select(std::pin::pin!(future), cancelled).await {
+            Either::Left((output, _)) => Some(output),
+            Either::Right(_) => None,
+        };
+
+        Ok(output)
+    }
+
+    /// Cancel the wait started with `id`, returning `false` if it is not running.
+    pub fn cancel(&self, id: u32) -> bool {
+        match self.cancellations.lock().unwrap().remove(&id) {
+            Some((_, sender)) => sender.send(()).is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// The id of a running [`Watchers::cancellable`] wait, removed once the wait ends or is dropped.
+struct CancelRegistration<'a> {
+    cancellations: &'a Mutex<HashMap<u32, (u64, oneshot::Sender<()>)>>,
+    id: u32,
+    token: u64,
+}
+
+impl Drop for CancelRegistration<'_> {
+    fn drop(&mut self) {
+        let mut cancellations = self.cancellations.lock().unwrap();
+
+        // Cancelled waits were removed already, the id may be used by a later wait since.
+        if cancellations
+            .get(&self.id)
+            .is_some_and(|(token, _)| *token == self.token)
+        {
+            cancellations.remove(&self.id);
+        }
+    }
+}
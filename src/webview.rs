@@ -0,0 +1,54 @@
+use tauri::{
+    webview::{PermissionKind as WebviewPermissionKind, PermissionResponse},
+    Runtime, Webview,
+};
+
+#[cfg(target_os = "macos")]
+use {
+    crate::{
+        models::{PermissionKind, PermissionStatus},
+        status::probe,
+    },
+    tauri::Manager,
+};
+
+/// Answer the webview's media capture requests from the system permission state.
+///
+/// Once microphone or camera permission is granted through this plugin, `getUserMedia`
+/// no longer shows WebKit's per-origin prompt, the same goes for screen recording
+/// permission and `getDisplayMedia`. Requests are denied when the system permission
+/// is denied, and left to the webview while it is not determined yet.
+///
+/// # Example
+/// ```
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_macos_permissions::init())
+///     .on_permission_request(tauri_plugin_macos_permissions::webview_permission_handler)
+/// ```
+pub fn webview_permission_handler<R: Runtime>(
+    webview: Webview<R>,
+    kind: WebviewPermissionKind,
+) -> PermissionResponse {
+    #[cfg(target_os = "macos")]
+    {
+        let kind = match kind {
+            WebviewPermissionKind::Microphone => PermissionKind::Microphone,
+            WebviewPermissionKind::Camera => PermissionKind::Camera,
+            WebviewPermissionKind::DisplayCapture => PermissionKind::ScreenRecording,
+            _ => return PermissionResponse::Default,
+        };
+
+        match probe(webview.app_handle(), kind).0 {
+            PermissionStatus::Granted => PermissionResponse::Allow,
+            PermissionStatus::Denied | PermissionStatus::Restricted => PermissionResponse::Deny,
+            _ => PermissionResponse::Default,
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (webview, kind);
+
+        PermissionResponse::Default
+    }
+}
@@ -0,0 +1,14 @@
+// swift-tools-version:5.5
+
+import PackageDescription
+
+let package = Package(
+    name: "MacosPermissions",
+    platforms: [.macOS(.v10_15)],
+    products: [
+        .library(name: "MacosPermissions", type: .static, targets: ["MacosPermissions"]),
+    ],
+    targets: [
+        .target(name: "MacosPermissions", path: "src"),
+    ]
+)
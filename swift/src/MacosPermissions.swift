@@ -0,0 +1,93 @@
+import CoreGraphics
+import Foundation
+import ScreenCaptureKit
+import UserNotifications
+
+/// Returned when the API is not available on the running system, the objc path is used instead.
+private let unavailable = -1
+
+private final class Box<T>: @unchecked Sendable {
+    var value: T?
+}
+
+/// Run `operation` to completion from a synchronous caller, giving up after `timeout` seconds.
+private func blocking<T>(timeout: TimeInterval = 5, _ operation: @escaping () async -> T) -> T? {
+    let semaphore = DispatchSemaphore(value: 0)
+    let result = Box<T>()
+
+    Task {
+        result.value = await operation()
+
+        semaphore.signal()
+    }
+
+    guard semaphore.wait(timeout: .now() + timeout) == .success else {
+        return nil
+    }
+
+    return result.value
+}
+
+/// Returns the raw `UNAuthorizationStatus`.
+@_cdecl("macos_permissions_notification_authorization_status")
+public func notificationAuthorizationStatus() -> Int {
+    // UNUserNotificationCenter raises an exception when the process is not an app bundle.
+    guard #available(macOS 12.0, *), Bundle.main.bundleIdentifier != nil else {
+        return unavailable
+    }
+
+    return blocking {
+        await UNUserNotificationCenter.current().notificationSettings().authorizationStatus.rawValue
+    } ?? unavailable
+}
+
+/// Capture the main display with ScreenCaptureKit.
+///
+/// Returns `0` if the frame has content, `1` if it is black and `2` if no frame could be captured.
+@_cdecl("macos_permissions_capture_main_display")
+public func captureMainDisplay() -> Int {
+    guard #available(macOS 14.0, *) else {
+        return unavailable
+    }
+
+    return blocking {
+        do {
+            let content = try await SCShareableContent.excludingDesktopWindows(false, onScreenWindowsOnly: true)
+
+            guard let display = content.displays.first(where: { $0.displayID == CGMainDisplayID() }) else {
+                return 2
+            }
+
+            let configuration = SCStreamConfiguration()
+
+            configuration.width = display.width
+            configuration.height = display.height
+
+            let image = try await SCScreenshotManager.captureImage(
+                contentFilter: SCContentFilter(display: display, excludingWindows: []),
+                configuration: configuration
+            )
+
+            return isBlack(image) ? 1 : 0
+        } catch {
+            return 2
+        }
+    } ?? 2
+}
+
+/// Sample the 32-bit pixels, ignoring the alpha channel.
+private func isBlack(_ image: CGImage) -> Bool {
+    guard let data = image.dataProvider?.data, let bytes = CFDataGetBytePtr(data) else {
+        return true
+    }
+
+    let length = CFDataGetLength(data)
+
+    for offset in stride(from: 0, to: length - 3, by: 4 * 97) {
+        if bytes[offset] != 0 || bytes[offset + 1] != 0 || bytes[offset + 2] != 0 {
+            return false
+        }
+    }
+
+    return true
+}